@@ -1,14 +1,14 @@
 //! Python bindings for the convolution operation.
 
-use ndarray::Axis;
+use ndarray::{ArrayD, Axis};
 use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
 use pyo3::prelude::*;
 use rayon::ThreadPoolBuilder;
 
 // local
-use crate::bindings::utils::{array_d_to_py_array, py_array_to_array_d};
+use crate::bindings::utils::{array_d_to_py_array, parse_pad_mode, py_array_to_array_d};
 use crate::core::convolution::convolution;
-use crate::core::padding::{PaddingMode, SlidingWorkspace};
+use crate::core::padding::{ConvMode, SlidingWorkspace};
 
 /// Compute the N-dimensional convolution of an input array with a weighted kernel.
 /// NaN values in the input are ignored in the convolution operation.
@@ -22,22 +22,42 @@ use crate::core::padding::{PaddingMode, SlidingWorkspace};
 /// kernel : numpy.ndarray[float64]
 ///    Kernel (weights) array with the same number of dimensions as ``data``.
 /// pad_mode: str
-///    Padding mode to use. One of 'constant', 'reflect' or 'replicate'.
+///    Padding mode to use. One of 'constant', 'reflect', 'replicate', 'wrap', 'symmetric',
+///    'mean', 'maximum', or 'minimum'.
 /// pad_value : float64
 ///    Constant value used to pad the borders of ``data``. Used when pad_mode is set to 'constant'.
 /// neumaier: bool
 ///    Whether to use Neumaier summation for the convolution calculation. This can improve the
 ///    numerical stability of the calculations, especially for large kernels or data with large values.
 ///    However, it it will be slightly slower than the standard summation.
+/// conv_mode : str | None
+///    NumPy/SciPy-style boundary convention, one of 'same', 'valid', or 'full'. 'same' (the
+///    default when None) keeps the stride-1 output length equal to ``data``'s, 'valid' applies no
+///    padding (output shrinks), 'full' pads by ``(k - 1) * dilation`` on both sides. Unlike
+///    ``pad_mode``, this also determines the split between low-side and high-side padding, so it
+///    matters for even-length kernels.
+/// stride : list[int] | None
+///    Per-dimension output stride (downsampling). If None, every output position is computed
+///    (stride 1 on every axis).
+/// dilation : list[int] | None
+///    Per-dimension kernel tap spacing for atrous/dilated convolution. If None, kernel taps are
+///    adjacent (dilation 1 on every axis).
 /// num_threads: int | None
 ///     the number of threads to use in the convolution. If None, uses the number of available
 ///     logical units.
+/// allow_separable : bool
+///    If True, attempt a rank-1 separable factorization of the kernel and, when it succeeds, run
+///    ``n`` cheap 1D passes instead of the dense ``O(prod(kernel.shape))`` loop. This is only
+///    exactly equivalent to the dense result when ``data`` has no NaNs, since a NaN masked out of
+///    one 1D pass affects later passes differently than masking it once in the full window;
+///    non-separable kernels always fall back to the dense path regardless of this flag.
 ///
 /// Returns
 /// ----------
 /// numpy.ndarray[float64]
-///    Array with the same shape as ``data`` containing the convolution result.
+///    Array containing the convolution result; shape depends on ``conv_mode`` and ``stride``.
 #[pyfunction(name = "convolution")]
+#[allow(clippy::too_many_arguments)]
 pub fn py_convolution<'py>(
     py: Python<'py>,
     data: PyReadonlyArrayDyn<'py, f64>,
@@ -45,9 +65,13 @@ pub fn py_convolution<'py>(
     pad_mode: &str,
     pad_value: f64,
     neumaier: bool,
+    conv_mode: Option<&str>,
+    stride: Option<Vec<usize>>,
+    dilation: Option<Vec<usize>>,
     num_threads: Option<usize>,
+    allow_separable: bool,
 ) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
-    let mut data_arr = py_array_to_array_d(&data)?;
+    let data_arr = py_array_to_array_d(&data)?;
     let mut kernel_arr = py_array_to_array_d(&kernel)?;
 
     // invert as the actual convolution function does a correlation operation.
@@ -56,51 +80,46 @@ pub fn py_convolution<'py>(
     }
 
     // pad
-    let padding_mode = match pad_mode {
-        "constant" => PaddingMode::Constant(pad_value),
-        "reflect" => PaddingMode::Reflect,
-        "replicate" => PaddingMode::Replicate,
-        _ => {
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+    let conv_mode = match conv_mode {
+        Some("same") | None => ConvMode::Same,
+        Some("valid") => ConvMode::Valid,
+        Some("full") => ConvMode::Full,
+        Some(other) => {
             let args = format!(
-                "Invalid padding mode: {}. Must be one of 'constant', 'reflect', 'replicate', or 'wrap'.",
-                pad_mode,
+                "Invalid conv_mode: {}. Must be one of 'same', 'valid', or 'full'.",
+                other,
             );
             return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
         }
     };
+    let ndim = data_arr.ndim();
+    let stride = stride.unwrap_or_else(|| vec![1; ndim]);
+    let dilation = dilation.unwrap_or_else(|| vec![1; ndim]);
+
+    let compute = move || -> ArrayD<f64> {
+        let workspace = SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+        let mut padded = workspace
+            .with_conv_mode(conv_mode, stride, dilation)
+            .unwrap();
+        padded.pad_input(data_arr.view());
+
+        let mut out = ArrayD::zeros(padded.out_shape.clone());
+        convolution(&padded, out.view_mut(), neumaier, allow_separable);
+        out
+    };
 
     // threads
-    match num_threads {
+    let result = match num_threads {
         Some(n) => {
             let pool = ThreadPoolBuilder::new()
                 .num_threads(n)
                 .build()
                 .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-
-            py.allow_threads(|| {
-                pool.install(|| {
-                    // padding
-                    let mut padded =
-                        SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
-                    padded.pad_input(data_arr.view());
-
-                    // convolution
-                    convolution(&padded, data_arr.view_mut(), neumaier);
-                })
-            });
+            py.allow_threads(|| pool.install(compute))
         }
-        None => {
-            py.allow_threads(|| {
-                // padding
-                let mut padded =
-                    SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
-                padded.pad_input(data_arr.view());
-
-                // convolution
-                convolution(&padded, data_arr.view_mut(), neumaier);
-            });
-        }
-    }
+        None => py.allow_threads(compute),
+    };
 
-    array_d_to_py_array(py, data_arr)
+    array_d_to_py_array(py, result)
 }
@@ -3,8 +3,14 @@
 // local
 pub mod convolution;
 pub mod padding;
+pub mod sliding_derivative;
 pub mod sliding_mean;
 pub mod sliding_median;
+pub mod sliding_min_max;
+pub mod sliding_quantile;
 pub mod sliding_sigma_clipping;
+pub mod sliding_skewness_kurtosis;
 pub mod sliding_standard_deviation;
+pub mod sliding_stats;
+pub mod sliding_weighted;
 mod utils;
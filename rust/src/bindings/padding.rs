@@ -4,8 +4,8 @@ use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
 use pyo3::prelude::*;
 
 // local
-use crate::bindings::utils::{array_d_to_py_array, py_array_to_array_d};
-use crate::core::padding::{PaddingMode, SlidingWorkspace};
+use crate::bindings::utils::{array_d_to_py_array, parse_pad_mode, py_array_to_array_d};
+use crate::core::padding::SlidingWorkspace;
 
 /// Adds padding to an N-dimensional array according to the specified kernel shape and padding
 /// option.
@@ -17,9 +17,13 @@ use crate::core::padding::{PaddingMode, SlidingWorkspace};
 ///    the kernel to use when doing the sliding operations (needs to have the same dimensionality
 ///    as ``data``).
 /// pad_mode: str
-///    the padding mode to use. Can be 'constant', 'reflect' or 'replicate'.
+///    the padding mode to use. Can be 'constant', 'reflect', 'replicate', 'wrap', 'symmetric', 'mean', 'maximum', or 'minimum'.
 /// pad_value : float64
 ///    Constant value used to pad the borders of ``data`` (only used when pad_mode is 'constant').
+/// dilation : list[int] | None
+///    Per-dimension kernel tap spacing (atrous); the halo of axis ``d`` becomes
+///    ``dilation[d] * ((k[d] - 1) // 2)`` on the low side and ``dilation[d] * (k[d] // 2)`` on the
+///    high side (equal for an odd kernel). If None, the halo is ``k[d] // 2`` on both sides.
 ///
 /// Returns
 /// ----------
@@ -32,24 +36,21 @@ pub fn py_padding<'py>(
     kernel: PyReadonlyArrayDyn<'py, f64>,
     pad_mode: &str,
     pad_value: f64,
+    dilation: Option<Vec<usize>>,
 ) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
     let data_arr = py_array_to_array_d(&data)?;
     let kernel_arr = py_array_to_array_d(&kernel)?;
 
     // pad
-    let padding_mode = match pad_mode {
-        "constant" => PaddingMode::Constant(pad_value),
-        "reflect" => PaddingMode::Reflect,
-        "replicate" => PaddingMode::Replicate,
-        _ => {
-            let args = format!(
-                "Invalid padding mode: {}. Must be one of 'constant', 'reflect', 'replicate', or 'wrap'.",
-                pad_mode,
-            );
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
-        }
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+    let ndim = data_arr.ndim();
+    let workspace = SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+    let mut padded = match dilation {
+        Some(dilation) => workspace
+            .with_stride_dilation(vec![1; ndim], dilation)
+            .unwrap(),
+        None => workspace,
     };
-    let mut padded = SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
     padded.pad_input(data_arr.view());
 
     // return the padded buffer as a new array
@@ -0,0 +1,88 @@
+//! Python bindings for the SBP sliding derivative operation.
+
+use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::prelude::*;
+
+// local
+use crate::bindings::utils::{array_d_to_py_array, py_array_to_array_d};
+use crate::core::sliding_derivative::{sliding_derivative, Symmetry};
+
+/// N-dimensional SBP (summation-by-parts) finite-difference derivative along `axis`.
+/// NaN values propagate: if any stencil/block input is NaN, the output point is NaN.
+///
+/// Parameters
+/// ----------
+/// data : numpy.ndarray[float64]
+///   Input N-dimensional array.
+/// axis : int
+///   Axis along which to differentiate.
+/// stencil : list[float]
+///   Interior diagonal stencil (odd length, centered on the output index), e.g.
+///   ``[-0.5, 0.0, 0.5]`` for the centered first derivative.
+/// block : list[list[float]]
+///   Dense boundary block: row ``i`` gives the coefficients applied to the leading samples to
+///   produce output point ``i``. The trailing points reuse the same rows reversed.
+/// antisymmetric : bool
+///   If ``True`` (the usual case for a first-derivative operator), the trailing block's
+///   contribution is negated; if ``False``, reused as-is.
+/// dx : float64
+///   Grid spacing; every result is scaled by ``1 / dx``.
+///
+/// Returns
+/// -------
+/// numpy.ndarray[float64]
+///  Array with the same shape as ``data`` containing the derivative result.
+#[pyfunction(name = "sliding_derivative")]
+pub fn py_sliding_derivative<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    axis: usize,
+    stencil: Vec<f64>,
+    block: Vec<Vec<f64>>,
+    antisymmetric: bool,
+    dx: f64,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let data_arr = py_array_to_array_d(&data)?;
+
+    if axis >= data_arr.ndim() {
+        let args = format!(
+            "axis {} is out of bounds for an array with {} dimensions.",
+            axis,
+            data_arr.ndim()
+        );
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+    }
+
+    if block.len() < stencil.len() / 2 {
+        let args = format!(
+            "block must have at least {} rows to cover the interior stencil's half-width, got {}.",
+            stencil.len() / 2,
+            block.len()
+        );
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+    }
+
+    let axis_len = data_arr.shape()[axis];
+    if axis_len < 2 * block.len() {
+        let args = format!(
+            "axis {} has length {}, too short for a boundary block of {} rows (need at least {}).",
+            axis,
+            axis_len,
+            block.len(),
+            2 * block.len()
+        );
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+    }
+
+    let symmetry = if antisymmetric {
+        Symmetry::Antisymmetric
+    } else {
+        Symmetry::Symmetric
+    };
+
+    let out = py.allow_threads(|| {
+        sliding_derivative(data_arr.view(), axis, &stencil, &block, symmetry, dx)
+    });
+
+    array_d_to_py_array(py, out)
+}
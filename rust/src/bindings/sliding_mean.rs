@@ -1,12 +1,15 @@
 //! Python bindings for the sliding mean operation.
 
-use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
+use numpy::PyReadonlyArrayDyn;
 use pyo3::prelude::*;
 use rayon::ThreadPoolBuilder;
 
 // local
-use crate::bindings::utils::{array_d_to_py_array, py_array_to_array_d};
-use crate::core::padding::{PaddingMode, SlidingWorkspace};
+use crate::bindings::utils::{
+    apply_mask, array_d_to_py_array_any_float, parse_pad_mode, py_array_to_array_d_any_float,
+    py_mask_to_array_d,
+};
+use crate::core::padding::SlidingWorkspace;
 use crate::core::sliding_mean::sliding_mean;
 
 /// N-dimensional sliding mean of an input array with a kernel.
@@ -16,52 +19,54 @@ use crate::core::sliding_mean::sliding_mean;
 ///
 /// Parameters
 /// ----------
-/// data : numpy.ndarray[float64]
-///    Input N-dimensional array.
-/// kernel : numpy.ndarray[float64]
+/// data : numpy.ndarray[float32 | float64]
+///    Input N-dimensional array. A ``float32`` array is computed in ``float64`` internally and
+///    the result is handed back as ``float32``, so a ``float32`` caller never pays for an
+///    up-cast it didn't ask for.
+/// kernel : numpy.ndarray[float32 | float64]
 ///    Kernel (weights) array with the same number of dimensions as ``data``.
+/// mask : numpy.ndarray[bool] | None
+///    Optional boolean array, same shape as ``data``. ``True`` marks a position to ignore, on
+///    top of (not instead of) any existing NaN values, following ``numpy.ma`` semantics.
 /// pad_mode: str
-///    the padding mode to use. Can be 'constant', 'reflect' or 'replicate'.
+///    the padding mode to use. Can be 'constant', 'reflect', 'replicate', 'wrap', 'symmetric', 'mean', 'maximum', or 'minimum'.
 /// pad_value : float64
 ///    Constant value used to pad the borders of ``data``. Used when pad_mode is set to 'constant'.
 /// neumaier: bool
 ///   Whether to use Neumaier summation for the sliding mean and standard deviation calculations.
 ///    This can improve the numerical stability of the calculations, especially for large kernels or
 ///   data with large values. However, it it will be slightly slower than the standard summation.
+/// allow_separable: bool
+///   Whether to opt into the rank-1 separable fast path when the kernel factors and the input has
+///   no NaNs (see ``convolution``'s flag of the same name for the NaN-equivalence caveat).
 /// num_threads: int | None
 ///     the number of threads to use in the sliding operation. If set to None, all available logical
 ///     units are used.
 ///
 /// Returns
 /// ----------
-/// numpy.ndarray[float64]
-///    Array with the same shape as ``data`` containing the sliding mean result.
+/// numpy.ndarray[float32 | float64]
+///    Array with the same shape and dtype as ``data`` containing the sliding mean result.
 #[pyfunction(name = "sliding_mean")]
 pub fn py_sliding_mean<'py>(
     py: Python<'py>,
-    data: PyReadonlyArrayDyn<'py, f64>,
-    kernel: PyReadonlyArrayDyn<'py, f64>,
+    data: &Bound<'py, PyAny>,
+    kernel: &Bound<'py, PyAny>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
     pad_mode: &str,
     pad_value: f64,
     neumaier: bool,
+    allow_separable: bool,
     num_threads: Option<usize>,
-) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
-    let mut data_arr = py_array_to_array_d(&data)?;
-    let kernel_arr = py_array_to_array_d(&kernel)?;
+) -> PyResult<Bound<'py, PyAny>> {
+    let (mut data_arr, data_dtype) = py_array_to_array_d_any_float(data)?;
+    let (kernel_arr, _) = py_array_to_array_d_any_float(kernel)?;
+    if let Some(mask) = &mask {
+        apply_mask(&mut data_arr, &py_mask_to_array_d(mask)?)?;
+    }
 
     // pad mode
-    let padding_mode = match pad_mode {
-        "constant" => PaddingMode::Constant(pad_value),
-        "reflect" => PaddingMode::Reflect,
-        "replicate" => PaddingMode::Replicate,
-        _ => {
-            let args = format!(
-                "Invalid padding mode: {}. Must be one of 'constant', 'reflect', 'replicate', or 'wrap'.",
-                pad_mode,
-            );
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
-        }
-    };
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
 
     // threads
     match num_threads {
@@ -79,7 +84,7 @@ pub fn py_sliding_mean<'py>(
                     padded.pad_input(data_arr.view());
 
                     // sliding mean
-                    sliding_mean(&padded, data_arr.view_mut(), neumaier);
+                    sliding_mean(&padded, data_arr.view_mut(), neumaier, allow_separable);
                 })
             });
         }
@@ -91,10 +96,10 @@ pub fn py_sliding_mean<'py>(
                 padded.pad_input(data_arr.view());
 
                 // sliding mean
-                sliding_mean(&padded, data_arr.view_mut(), neumaier);
+                sliding_mean(&padded, data_arr.view_mut(), neumaier, allow_separable);
             });
         }
     }
 
-    array_d_to_py_array(py, data_arr)
+    array_d_to_py_array_any_float(py, data_arr, data_dtype)
 }
@@ -1,12 +1,15 @@
 //! Python bindings for the sliding median operation.
 
-use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
+use numpy::PyReadonlyArrayDyn;
 use pyo3::prelude::*;
 use rayon::ThreadPoolBuilder;
 
 // local
-use crate::bindings::utils::{array_d_to_py_array, py_array_to_array_d};
-use crate::core::padding::{PaddingMode, SlidingWorkspace};
+use crate::bindings::utils::{
+    apply_mask, array_d_to_py_array_any_float, parse_pad_mode, py_array_to_array_d_any_float,
+    py_mask_to_array_d,
+};
+use crate::core::padding::SlidingWorkspace;
 use crate::core::sliding_median::sliding_median;
 
 /// N-dimensional sliding median of an input array with a kernel.
@@ -16,42 +19,39 @@ use crate::core::sliding_median::sliding_median;
 ///
 /// Parameters
 /// ----------
-/// data : numpy.ndarray[float64]
-///   Input N-dimensional array.
-/// kernel : numpy.ndarray[float64]
+/// data : numpy.ndarray[float32 | float64]
+///   Input N-dimensional array. A ``float32`` array is computed in ``float64`` internally and
+///   the result is handed back as ``float32``.
+/// kernel : numpy.ndarray[float32 | float64]
 ///  Kernel (weights) array with the same number of dimensions as ``data``.
+/// mask : numpy.ndarray[bool] | None
+///  Optional boolean array, same shape as ``data``. ``True`` marks a position to ignore, on top
+///  of (not instead of) any existing NaN values, following ``numpy.ma`` semantics.
 /// pad_value : float64
 ///  Constant value used to pad the borders of ``data``.
 ///
 /// Returns
 /// -------
-/// numpy.ndarray[float64]
-///  Array with the same shape as ``data`` containing the sliding median result.
+/// numpy.ndarray[float32 | float64]
+///  Array with the same shape and dtype as ``data`` containing the sliding median result.
 #[pyfunction(name = "sliding_median")]
 pub fn py_sliding_median<'py>(
     py: Python<'py>,
-    data: PyReadonlyArrayDyn<'py, f64>,
-    kernel: PyReadonlyArrayDyn<'py, f64>,
+    data: &Bound<'py, PyAny>,
+    kernel: &Bound<'py, PyAny>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
     pad_mode: &str,
     pad_value: f64,
     num_threads: Option<usize>,
-) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
-    let mut data_arr = py_array_to_array_d(&data)?;
-    let kernel_arr = py_array_to_array_d(&kernel)?;
+) -> PyResult<Bound<'py, PyAny>> {
+    let (mut data_arr, data_dtype) = py_array_to_array_d_any_float(data)?;
+    let (kernel_arr, _) = py_array_to_array_d_any_float(kernel)?;
+    if let Some(mask) = &mask {
+        apply_mask(&mut data_arr, &py_mask_to_array_d(mask)?)?;
+    }
 
     // pad
-    let padding_mode = match pad_mode {
-        "constant" => PaddingMode::Constant(pad_value),
-        "reflect" => PaddingMode::Reflect,
-        "replicate" => PaddingMode::Replicate,
-        _ => {
-            let args = format!(
-                "Invalid padding mode: {}. Must be one of 'constant', 'reflect', 'replicate', or 'wrap'.",
-                pad_mode,
-            );
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
-        }
-    };
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
 
     // threads
     match num_threads {
@@ -86,5 +86,5 @@ pub fn py_sliding_median<'py>(
         }
     }
 
-    array_d_to_py_array(py, data_arr)
+    array_d_to_py_array_any_float(py, data_arr, data_dtype)
 }
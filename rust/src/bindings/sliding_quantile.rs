@@ -0,0 +1,182 @@
+//! Python bindings for the sliding quantile operation.
+
+use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+// local
+use crate::bindings::utils::{array_d_to_py_array, parse_pad_mode, py_array_to_array_d};
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_quantile::{sliding_quantile, sliding_quantile_approx};
+
+/// N-dimensional sliding quantile of an input array with a kernel.
+/// NaN values in the input are ignored in the quantile calculation.
+/// If no valid values in the kernel window, the output is set to NaN.
+/// Kernel can contain weights (acting as a mask when 0, and as reliability weights otherwise).
+///
+/// Parameters
+/// ----------
+/// data : numpy.ndarray[float64]
+///   Input N-dimensional array.
+/// kernel : numpy.ndarray[float64]
+///  Kernel (weights) array with the same number of dimensions as ``data``.
+/// q : float64
+///  Target quantile, in ``0..=1`` (e.g. ``0.5`` for the median, ``0.25``/``0.75`` for the IQR
+///  bounds). Unweighted windows use numpy's default ``'linear'`` interpolation.
+/// pad_mode: str
+///    Padding mode to use. One of 'constant', 'reflect', 'replicate', 'wrap', 'symmetric',
+///    'mean', 'maximum', or 'minimum'.
+/// pad_value : float64
+///  Constant value used to pad the borders of ``data``.
+///
+/// Returns
+/// -------
+/// numpy.ndarray[float64]
+///  Array with the same shape as ``data`` containing the sliding quantile result.
+#[pyfunction(name = "sliding_quantile")]
+pub fn py_sliding_quantile<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    q: f64,
+    pad_mode: &str,
+    pad_value: f64,
+    num_threads: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let mut data_arr = py_array_to_array_d(&data)?;
+    let kernel_arr = py_array_to_array_d(&kernel)?;
+
+    if !(0.0..=1.0).contains(&q) {
+        let args = format!("q must be between 0 and 1 (inclusive), got {}.", q);
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+    }
+
+    // pad
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+
+    // threads
+    match num_threads {
+        Some(n) => {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            py.allow_threads(|| {
+                pool.install(|| {
+                    // padding
+                    let mut padded =
+                        SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                    padded.pad_input(data_arr.view());
+
+                    // sliding quantile
+                    sliding_quantile(&padded, data_arr.view_mut(), q);
+                })
+            });
+        }
+        None => {
+            py.allow_threads(|| {
+                // padding
+                let mut padded =
+                    SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                padded.pad_input(data_arr.view());
+
+                // sliding quantile
+                sliding_quantile(&padded, data_arr.view_mut(), q);
+            });
+        }
+    }
+
+    array_d_to_py_array(py, data_arr)
+}
+
+/// N-dimensional sliding **approximate** quantile of an input array with a kernel, via a
+/// Greenwald-Khanna epsilon-summary per window. Bounds per-window cost for very large kernels
+/// instead of fully sorting every window, at the cost of an `epsilon * n`-rank approximation
+/// error. NaN values are ignored; kernel entries equal to 0 act as a mask.
+///
+/// Parameters
+/// ----------
+/// data : numpy.ndarray[float64]
+///   Input N-dimensional array.
+/// kernel : numpy.ndarray[float64]
+///  Kernel (mask) array with the same number of dimensions as ``data``.
+/// q : float64
+///  Target quantile, in ``0..=1``.
+/// epsilon : float64
+///  Approximation error bound, in ``0..=1``. Smaller is more accurate but keeps more tuples
+///  per window's epsilon-summary.
+/// pad_mode: str
+///    Padding mode to use. One of 'constant', 'reflect', 'replicate', 'wrap', 'symmetric',
+///    'mean', 'maximum', or 'minimum'.
+/// pad_value : float64
+///  Constant value used to pad the borders of ``data``.
+///
+/// Returns
+/// -------
+/// numpy.ndarray[float64]
+///  Array with the same shape as ``data`` containing the approximate sliding quantile result.
+#[pyfunction(name = "sliding_quantile_approx")]
+pub fn py_sliding_quantile_approx<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    q: f64,
+    epsilon: f64,
+    pad_mode: &str,
+    pad_value: f64,
+    num_threads: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let mut data_arr = py_array_to_array_d(&data)?;
+    let kernel_arr = py_array_to_array_d(&kernel)?;
+
+    if !(0.0..=1.0).contains(&q) {
+        let args = format!("q must be between 0 and 1 (inclusive), got {}.", q);
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+    }
+    if !(0.0..=1.0).contains(&epsilon) {
+        let args = format!(
+            "epsilon must be between 0 and 1 (inclusive), got {}.",
+            epsilon
+        );
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+    }
+
+    // pad
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+
+    // threads
+    match num_threads {
+        Some(n) => {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            py.allow_threads(|| {
+                pool.install(|| {
+                    // padding
+                    let mut padded =
+                        SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                    padded.pad_input(data_arr.view());
+
+                    // sliding approximate quantile
+                    sliding_quantile_approx(&padded, data_arr.view_mut(), q, epsilon);
+                })
+            });
+        }
+        None => {
+            py.allow_threads(|| {
+                // padding
+                let mut padded =
+                    SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                padded.pad_input(data_arr.view());
+
+                // sliding approximate quantile
+                sliding_quantile_approx(&padded, data_arr.view_mut(), q, epsilon);
+            });
+        }
+    }
+
+    array_d_to_py_array(py, data_arr)
+}
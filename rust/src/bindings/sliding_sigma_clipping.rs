@@ -1,13 +1,17 @@
 //! Python bindings for the sliding sigma clipping operation.
 
+use ndarray::Zip;
 use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
 use pyo3::prelude::*;
 use rayon::ThreadPoolBuilder;
 
 // local
-use crate::bindings::utils::{array_d_to_py_array, py_array_to_array_d};
-use crate::core::padding::{PaddingMode, SlidingWorkspace};
-use crate::core::sliding_sigma_clipping::{CenterMode, sliding_sigma_clipping};
+use crate::bindings::utils::{
+    apply_mask, array_d_to_py_array, array_d_to_py_array_any_float, parse_pad_mode,
+    py_array_to_array_d_any_float, py_mask_to_array_d,
+};
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_sigma_clipping::{sliding_sigma_clipping, CenterMode};
 
 /// N-dimensional sliding sigma clipping of an input array with a kernel.
 /// The clipped values are set to the corresponding final sliding mode value.
@@ -19,14 +23,20 @@ use crate::core::sliding_sigma_clipping::{CenterMode, sliding_sigma_clipping};
 ///
 /// Parameters
 /// ----------
-/// data : numpy.ndarray[float64]
-///   Input N-dimensional array.
-/// kernel : numpy.ndarray[float64]
+/// data : numpy.ndarray[float32 | float64]
+///   Input N-dimensional array. A ``float32`` array is computed in ``float64`` internally and
+///   the clipped result is handed back as ``float32``.
+/// kernel : numpy.ndarray[float32 | float64]
 ///    Kernel (weights) array with the same number of dimensions as ``data``.
+/// mask : numpy.ndarray[bool] | None
+///    Optional boolean array, same shape as ``data``. ``True`` marks a position to ignore, on
+///    top of (not instead of) any existing NaN values, following ``numpy.ma`` semantics. Folded
+///    into the returned clip mask, so the result is expressible as a single updated mask instead
+///    of only as overwritten values.
 /// center_mode: str
 ///   the sliding mode to use for the clipping. Can be 'mean' or 'median'.
 /// pad_mode: str
-///    the padding mode to use. Can be 'constant', 'reflect' or 'replicate'.
+///    the padding mode to use. Can be 'constant', 'reflect', 'replicate', 'wrap', 'symmetric', 'mean', 'maximum', or 'minimum'.
 /// pad_value : float64
 ///    Constant value used to pad the borders of ``data``. Used when pad_mode is set to 'constant'.
 /// neumaier: bool
@@ -45,14 +55,15 @@ use crate::core::sliding_sigma_clipping::{CenterMode, sliding_sigma_clipping};
 ///
 /// Returns
 /// -------
-/// tuple[numpy.ndarray[float64], numpy.ndarray[bool]]
-///     Array with the same shape as ``data`` containing the sigma clipped result.
-///     Bool array with the clipped positions.
+/// tuple[numpy.ndarray[float32 | float64], numpy.ndarray[bool]]
+///     Array with the same shape and dtype as ``data`` containing the sigma clipped result.
+///     Bool array with the clipped positions, ORed with ``mask`` if one was given.
 #[pyfunction(name = "sliding_sigma_clipping")]
 pub fn py_sliding_sigma_clipping<'py>(
     py: Python<'py>,
-    data: PyReadonlyArrayDyn<'py, f64>,
-    kernel: PyReadonlyArrayDyn<'py, f64>,
+    data: &Bound<'py, PyAny>,
+    kernel: &Bound<'py, PyAny>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
     center_mode: &str,
     pad_mode: &str,
     pad_value: f64,
@@ -61,24 +72,21 @@ pub fn py_sliding_sigma_clipping<'py>(
     sigma_lower: Option<f64>,
     max_iterations: Option<usize>,
     num_threads: Option<usize>,
-) -> PyResult<(Bound<'py, PyArrayDyn<f64>>, Bound<'py, PyArrayDyn<bool>>)> {
-    let mut data_arr = py_array_to_array_d(&data)?;
-    let kernel_arr = py_array_to_array_d(&kernel)?;
-
-    // pad mode
-    let padding_mode = match pad_mode {
-        "constant" => PaddingMode::Constant(pad_value),
-        "reflect" => PaddingMode::Reflect,
-        "replicate" => PaddingMode::Replicate,
-        _ => {
-            let args = format!(
-                "Invalid padding mode: {}. Must be one of 'constant', 'reflect', 'replicate', or 'wrap'.",
-                pad_mode,
-            );
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyArrayDyn<bool>>)> {
+    let (mut data_arr, data_dtype) = py_array_to_array_d_any_float(data)?;
+    let (kernel_arr, _) = py_array_to_array_d_any_float(kernel)?;
+    let mask_arr = match &mask {
+        Some(mask) => {
+            let mask_arr = py_mask_to_array_d(mask)?;
+            apply_mask(&mut data_arr, &mask_arr)?;
+            Some(mask_arr)
         }
+        None => None,
     };
 
+    // pad mode
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+
     // center mode
     let center_mode = match center_mode {
         "mean" => CenterMode::Mean,
@@ -91,7 +99,7 @@ pub fn py_sliding_sigma_clipping<'py>(
     };
 
     // threads
-    let changed_mask = match num_threads {
+    let mut changed_mask = match num_threads {
         Some(n) => {
             let pool = ThreadPoolBuilder::new()
                 .num_threads(n)
@@ -139,7 +147,13 @@ pub fn py_sliding_sigma_clipping<'py>(
         }
     };
 
-    let sigma_clipped = array_d_to_py_array(py, data_arr)?;
+    if let Some(mask_arr) = mask_arr {
+        Zip::from(&mut changed_mask)
+            .and(&mask_arr)
+            .for_each(|c, &m| *c |= m);
+    }
+
+    let sigma_clipped = array_d_to_py_array_any_float(py, data_arr, data_dtype)?;
     let changed_mask = array_d_to_py_array(py, changed_mask)?;
     Ok((sigma_clipped, changed_mask))
 }
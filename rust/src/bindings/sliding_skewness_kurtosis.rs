@@ -0,0 +1,114 @@
+//! Python bindings for the sliding skewness/kurtosis operations.
+
+use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+// local
+use crate::bindings::utils::{array_d_to_py_array, parse_pad_mode, py_array_to_array_d};
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_skewness_kurtosis::{sliding_kurtosis, sliding_skewness};
+
+/// N-dimensional sliding skewness (standardized third central moment) of an input array with a
+/// kernel. NaN values are ignored. Kernel entries are used as a 0/nonzero mask only. A window
+/// with fewer than 2 valid values or zero variance yields NaN.
+///
+/// Parameters
+/// ----------
+/// data : numpy.ndarray[float64]
+///   Input N-dimensional array.
+/// kernel : numpy.ndarray[float64]
+///  Kernel (mask) array with the same number of dimensions as ``data``.
+/// pad_value : float64
+///  Constant value used to pad the borders of ``data``.
+///
+/// Returns
+/// -------
+/// numpy.ndarray[float64]
+///  Array with the same shape as ``data`` containing the sliding skewness result.
+#[pyfunction(name = "sliding_skewness")]
+pub fn py_sliding_skewness<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    pad_mode: &str,
+    pad_value: f64,
+    num_threads: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    run_moment(
+        py,
+        data,
+        kernel,
+        pad_mode,
+        pad_value,
+        num_threads,
+        sliding_skewness,
+    )
+}
+
+/// N-dimensional sliding excess kurtosis (standardized fourth central moment minus 3) of an input
+/// array with a kernel. See `py_sliding_skewness` for the NaN and kernel conventions.
+#[pyfunction(name = "sliding_kurtosis")]
+pub fn py_sliding_kurtosis<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    pad_mode: &str,
+    pad_value: f64,
+    num_threads: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    run_moment(
+        py,
+        data,
+        kernel,
+        pad_mode,
+        pad_value,
+        num_threads,
+        sliding_kurtosis,
+    )
+}
+
+fn run_moment<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    pad_mode: &str,
+    pad_value: f64,
+    num_threads: Option<usize>,
+    op: fn(&SlidingWorkspace, ndarray::ArrayViewMutD<f64>),
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let mut data_arr = py_array_to_array_d(&data)?;
+    let kernel_arr = py_array_to_array_d(&kernel)?;
+
+    // pad mode
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+
+    // threads
+    match num_threads {
+        Some(n) => {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            py.allow_threads(|| {
+                pool.install(|| {
+                    let mut padded =
+                        SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                    padded.pad_input(data_arr.view());
+                    op(&padded, data_arr.view_mut());
+                })
+            });
+        }
+        None => {
+            py.allow_threads(|| {
+                let mut padded =
+                    SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                padded.pad_input(data_arr.view());
+                op(&padded, data_arr.view_mut());
+            });
+        }
+    }
+
+    array_d_to_py_array(py, data_arr)
+}
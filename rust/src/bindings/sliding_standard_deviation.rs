@@ -1,14 +1,17 @@
 //! Python bindings for the sliding standard deviation operation.
 
 use ndarray::ArrayD;
-use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
+use numpy::PyReadonlyArrayDyn;
 use pyo3::prelude::*;
 use rayon::ThreadPoolBuilder;
 
 // local
-use crate::bindings::utils::{array_d_to_py_array, py_array_to_array_d};
-use crate::core::padding::{PaddingMode, SlidingWorkspace};
-use crate::core::sliding_standard_deviation::sliding_standard_deviation;
+use crate::bindings::utils::{
+    apply_mask, array_d_to_py_array_any_float, parse_pad_mode, py_array_to_array_d_any_float,
+    py_mask_to_array_d,
+};
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_standard_deviation::{sliding_standard_deviation, VarianceDenominator};
 
 /// N-dimensional sliding standard deviation of an input array with a kernel.
 /// NaN values in the input are ignored in the standard deviation calculation.
@@ -17,43 +20,59 @@ use crate::core::sliding_standard_deviation::sliding_standard_deviation;
 ///
 /// Parameters
 /// ----------
-/// data : numpy.ndarray[float64]
-///  Input N-dimensional array.
-/// kernel : numpy.ndarray[float64]
+/// data : numpy.ndarray[float32 | float64]
+///  Input N-dimensional array. A ``float32`` array is computed in ``float64`` internally and
+///  both returned arrays are handed back as ``float32``.
+/// kernel : numpy.ndarray[float32 | float64]
 /// Kernel (weights) array with the same number of dimensions as ``data``.
+/// mask : numpy.ndarray[bool] | None
+/// Optional boolean array, same shape as ``data``. ``True`` marks a position to ignore, on top
+/// of (not instead of) any existing NaN values, following ``numpy.ma`` semantics.
 /// pad_value : float64
 /// Constant value used to pad the borders of ``data``.
+/// sample_variance : bool
+/// If ``False`` (default), uses the population denominator ``W``. If ``True``, uses the
+/// reliability-weighted sample denominator ``W - sum(w^2) / W``, appropriate when the kernel
+/// weights represent per-sample reliabilities rather than repeat counts. Ignored when ``ddof``
+/// is given.
+/// ddof : int, optional
+/// When given, overrides ``sample_variance`` and uses ``M2 / (n - ddof)`` where ``n`` is the
+/// plain count of valid samples in the window (ignoring their weight magnitude) — ``ddof=0``
+/// is the population variance, ``ddof=1`` the usual Bessel-corrected sample variance.
+/// neumaier : bool
+/// Whether to use Neumaier-compensated summation for the running weight totals.
 ///
 /// Returns
 /// -------
-/// Tuple of two numpy.ndarray[float64]:
+/// Tuple of two numpy.ndarray[float32 | float64], matching ``data``'s dtype:
 /// 1. Array with the same shape as ``data`` containing the sliding standard deviation result
 /// 2. Array with the same shape as ``data`` containing the sliding mean result (used in standard deviation calculation)
 #[pyfunction(name = "sliding_standard_deviation")]
 pub fn py_sliding_standard_deviation<'py>(
     py: Python<'py>,
-    data: PyReadonlyArrayDyn<'py, f64>,
-    kernel: PyReadonlyArrayDyn<'py, f64>,
+    data: &Bound<'py, PyAny>,
+    kernel: &Bound<'py, PyAny>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
     pad_mode: &str,
     pad_value: f64,
+    sample_variance: bool,
+    ddof: Option<usize>,
+    neumaier: bool,
     num_threads: Option<usize>,
-) -> PyResult<(Bound<'py, PyArrayDyn<f64>>, Bound<'py, PyArrayDyn<f64>>)> {
-    let mut data_arr = py_array_to_array_d(&data)?;
-    let kernel_arr = py_array_to_array_d(&kernel)?;
+) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>)> {
+    let (mut data_arr, data_dtype) = py_array_to_array_d_any_float(data)?;
+    let (kernel_arr, _) = py_array_to_array_d_any_float(kernel)?;
+    if let Some(mask) = &mask {
+        apply_mask(&mut data_arr, &py_mask_to_array_d(mask)?)?;
+    }
     let mut mean_buffer = ArrayD::zeros(data_arr.shape());
 
     // pad mode
-    let padding_mode = match pad_mode {
-        "constant" => PaddingMode::Constant(pad_value),
-        "reflect" => PaddingMode::Reflect,
-        "replicate" => PaddingMode::Replicate,
-        _ => {
-            let args = format!(
-                "Invalid padding mode: {}. Must be one of 'constant', 'reflect', 'replicate', or 'wrap'.",
-                pad_mode,
-            );
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
-        }
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+    let denominator = match ddof {
+        Some(d) => VarianceDenominator::Ddof(d),
+        None if sample_variance => VarianceDenominator::ReliabilitySample,
+        None => VarianceDenominator::Population,
     };
 
     // threads
@@ -76,6 +95,8 @@ pub fn py_sliding_standard_deviation<'py>(
                         &padded,
                         data_arr.view_mut(),
                         mean_buffer.view_mut(),
+                        denominator,
+                        neumaier,
                     );
                 })
             });
@@ -88,12 +109,18 @@ pub fn py_sliding_standard_deviation<'py>(
                 padded.pad_input(data_arr.view());
 
                 // sliding standard deviation
-                sliding_standard_deviation(&padded, data_arr.view_mut(), mean_buffer.view_mut());
+                sliding_standard_deviation(
+                    &padded,
+                    data_arr.view_mut(),
+                    mean_buffer.view_mut(),
+                    denominator,
+                    neumaier,
+                );
             });
         }
     }
 
-    let standard_deviation = array_d_to_py_array(py, data_arr)?;
-    let mean = array_d_to_py_array(py, mean_buffer)?;
+    let standard_deviation = array_d_to_py_array_any_float(py, data_arr, data_dtype)?;
+    let mean = array_d_to_py_array_any_float(py, mean_buffer, data_dtype)?;
     Ok((standard_deviation, mean))
 }
@@ -0,0 +1,99 @@
+//! Python bindings for the fused multi-statistic sliding pass.
+
+use numpy::PyReadonlyArrayDyn;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::ThreadPoolBuilder;
+
+// local
+use crate::bindings::utils::{array_d_to_py_array, parse_pad_mode, py_array_to_array_d};
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_stats::{sliding_stats, Stat};
+
+fn parse_stat(name: &str) -> PyResult<Stat> {
+    match name {
+        "mean" => Ok(Stat::Mean),
+        "variance" => Ok(Stat::Variance),
+        "std" => Ok(Stat::Std),
+        "min" => Ok(Stat::Min),
+        "max" => Ok(Stat::Max),
+        "count" => Ok(Stat::Count),
+        _ => {
+            let args = format!(
+                "Invalid statistic: {}. Must be one of 'mean', 'variance', 'std', 'min', 'max', 'count'.",
+                name,
+            );
+            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args))
+        }
+    }
+}
+
+/// Computes any requested subset of {mean, variance, std, min, max, count} over a sliding window,
+/// sharing a single traversal of the padded buffer (and a single Welford/West accumulation)
+/// instead of padding and iterating once per statistic.
+///
+/// Parameters
+/// ----------
+/// data : numpy.ndarray[float64]
+///   Input N-dimensional array.
+/// kernel : numpy.ndarray[float64]
+///  Kernel (weights) array with the same number of dimensions as ``data``.
+/// stats : list[str]
+///  Subset of {'mean', 'variance', 'std', 'min', 'max', 'count'} to compute.
+/// pad_value : float64
+///  Constant value used to pad the borders of ``data``.
+///
+/// Returns
+/// -------
+/// dict[str, numpy.ndarray[float64]]
+///  One entry per requested statistic, each an array with the same shape as ``data``.
+#[pyfunction(name = "sliding_stats")]
+pub fn py_sliding_stats<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    stats: Vec<String>,
+    pad_mode: &str,
+    pad_value: f64,
+    num_threads: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let data_arr = py_array_to_array_d(&data)?;
+    let kernel_arr = py_array_to_array_d(&kernel)?;
+    let requested: Vec<Stat> = stats
+        .iter()
+        .map(|s| parse_stat(s))
+        .collect::<PyResult<_>>()?;
+
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+
+    let compute = |data_arr: &ndarray::ArrayD<f64>| {
+        let mut padded = SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+        padded.pad_input(data_arr.view());
+        sliding_stats(&padded, &requested)
+    };
+
+    let results = match num_threads {
+        Some(n) => {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            py.allow_threads(|| pool.install(|| compute(&data_arr)))
+        }
+        None => py.allow_threads(|| compute(&data_arr)),
+    };
+
+    let out = PyDict::new_bound(py);
+    for (stat, array) in results {
+        let key = match stat {
+            Stat::Mean => "mean",
+            Stat::Variance => "variance",
+            Stat::Std => "std",
+            Stat::Min => "min",
+            Stat::Max => "max",
+            Stat::Count => "count",
+        };
+        out.set_item(key, array_d_to_py_array(py, array)?)?;
+    }
+    Ok(out)
+}
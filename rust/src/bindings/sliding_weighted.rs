@@ -0,0 +1,169 @@
+//! Python bindings for the explicitly-named sliding weighted mean/standard deviation operations.
+
+use ndarray::ArrayD;
+use numpy::{PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+// local
+use crate::bindings::utils::{array_d_to_py_array, parse_pad_mode, py_array_to_array_d};
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_weighted::{sliding_weighted_mean, sliding_weighted_standard_deviation};
+
+/// N-dimensional sliding weighted mean of an input array with a kernel of real-valued weights.
+/// NaN values and weight-0 kernel entries are ignored. If no valid values in the kernel window,
+/// the output is set to NaN.
+///
+/// Parameters
+/// ----------
+/// data : numpy.ndarray[float64]
+///    Input N-dimensional array.
+/// kernel : numpy.ndarray[float64]
+///    Kernel (weights) array with the same number of dimensions as ``data``.
+/// pad_value : float64
+///    Constant value used to pad the borders of ``data``.
+///
+/// Returns
+/// -------
+/// numpy.ndarray[float64]
+///    Array with the same shape as ``data`` containing the sliding weighted mean result.
+#[pyfunction(name = "sliding_weighted_mean")]
+pub fn py_sliding_weighted_mean<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    pad_mode: &str,
+    pad_value: f64,
+    neumaier: bool,
+    num_threads: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let mut data_arr = py_array_to_array_d(&data)?;
+    let kernel_arr = py_array_to_array_d(&kernel)?;
+
+    // pad mode
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+
+    // threads
+    match num_threads {
+        Some(n) => {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            py.allow_threads(|| {
+                pool.install(|| {
+                    // padding
+                    let mut padded =
+                        SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                    padded.pad_input(data_arr.view());
+
+                    // sliding weighted mean
+                    sliding_weighted_mean(&padded, data_arr.view_mut(), neumaier);
+                })
+            });
+        }
+        None => {
+            py.allow_threads(|| {
+                // padding
+                let mut padded =
+                    SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                padded.pad_input(data_arr.view());
+
+                // sliding weighted mean
+                sliding_weighted_mean(&padded, data_arr.view_mut(), neumaier);
+            });
+        }
+    }
+
+    array_d_to_py_array(py, data_arr)
+}
+
+/// N-dimensional sliding weighted standard deviation of an input array with a kernel of
+/// real-valued weights. NaN values and weight-0 kernel entries are ignored. If no valid values in
+/// the kernel window, the output (and the mean) is set to NaN.
+///
+/// Parameters
+/// ----------
+/// data : numpy.ndarray[float64]
+///    Input N-dimensional array.
+/// kernel : numpy.ndarray[float64]
+///    Kernel (weights) array with the same number of dimensions as ``data``.
+/// pad_value : float64
+///    Constant value used to pad the borders of ``data``.
+/// sample_variance : bool
+///    If ``False`` (default), uses the population denominator ``Sum(w)``. If ``True``, uses the
+///    reliability-weighted sample denominator ``Sum(w) - Sum(w^2) / Sum(w)``.
+///
+/// Returns
+/// -------
+/// Tuple of two numpy.ndarray[float64]:
+/// 1. Array with the same shape as ``data`` containing the sliding weighted standard deviation
+/// 2. Array with the same shape as ``data`` containing the sliding weighted mean
+#[pyfunction(name = "sliding_weighted_standard_deviation")]
+pub fn py_sliding_weighted_standard_deviation<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    kernel: PyReadonlyArrayDyn<'py, f64>,
+    pad_mode: &str,
+    pad_value: f64,
+    sample_variance: bool,
+    neumaier: bool,
+    num_threads: Option<usize>,
+) -> PyResult<(Bound<'py, PyArrayDyn<f64>>, Bound<'py, PyArrayDyn<f64>>)> {
+    let mut data_arr = py_array_to_array_d(&data)?;
+    let kernel_arr = py_array_to_array_d(&kernel)?;
+    let mut mean_buffer = ArrayD::zeros(data_arr.shape());
+
+    // pad mode
+    let padding_mode = parse_pad_mode(pad_mode, pad_value)?;
+
+    // threads
+    match num_threads {
+        Some(n) => {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            py.allow_threads(|| {
+                pool.install(|| {
+                    // padding
+                    let mut padded =
+                        SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                    padded.pad_input(data_arr.view());
+
+                    // sliding weighted standard deviation
+                    sliding_weighted_standard_deviation(
+                        &padded,
+                        data_arr.view_mut(),
+                        mean_buffer.view_mut(),
+                        sample_variance,
+                        neumaier,
+                    );
+                })
+            });
+        }
+        None => {
+            py.allow_threads(|| {
+                // padding
+                let mut padded =
+                    SlidingWorkspace::new(data_arr.shape(), kernel_arr, padding_mode).unwrap();
+                padded.pad_input(data_arr.view());
+
+                // sliding weighted standard deviation
+                sliding_weighted_standard_deviation(
+                    &padded,
+                    data_arr.view_mut(),
+                    mean_buffer.view_mut(),
+                    sample_variance,
+                    neumaier,
+                );
+            });
+        }
+    }
+
+    let standard_deviation = array_d_to_py_array(py, data_arr)?;
+    let mean = array_d_to_py_array(py, mean_buffer)?;
+    Ok((standard_deviation, mean))
+}
@@ -5,30 +5,165 @@ use numpy::{PyArray1, PyArrayDyn, PyReadonlyArrayDyn, PyUntypedArrayMethods};
 use pyo3::prelude::*;
 use pyo3::types::PyTuple;
 
+// local
+use crate::core::padding::{PaddingMode, StatisticKind};
+
+/// Parses the `pad_mode`/`pad_value` pair every binding accepts into a `PaddingMode`, so the
+/// 8-arm match (and its error string) lives in one place instead of being copy-pasted per binding.
+///
+/// # Parameters
+/// - `pad_mode`: One of `'constant'`, `'reflect'`, `'replicate'`, `'wrap'`, `'symmetric'`,
+///   `'mean'`, `'maximum'`, or `'minimum'`.
+/// - `pad_value`: Constant value used when `pad_mode` is `'constant'`.
+///
+/// # Returns
+/// - `Err(PyValueError)` if `pad_mode` isn't one of the recognized strings.
+pub fn parse_pad_mode(pad_mode: &str, pad_value: f64) -> PyResult<PaddingMode> {
+    match pad_mode {
+        "constant" => Ok(PaddingMode::Constant(pad_value)),
+        "reflect" => Ok(PaddingMode::Reflect),
+        "replicate" => Ok(PaddingMode::Replicate),
+        "wrap" => Ok(PaddingMode::Wrap),
+        "symmetric" => Ok(PaddingMode::Symmetric),
+        "mean" => Ok(PaddingMode::Statistic(StatisticKind::Mean)),
+        "maximum" => Ok(PaddingMode::Statistic(StatisticKind::Maximum)),
+        "minimum" => Ok(PaddingMode::Statistic(StatisticKind::Minimum)),
+        _ => {
+            let args = format!(
+                "Invalid padding mode: {}. Must be one of 'constant', 'reflect', 'replicate', 'wrap', 'symmetric', 'mean', 'maximum', or 'minimum'.",
+                pad_mode,
+            );
+            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args))
+        }
+    }
+}
+
+/// Element dtype of a NumPy array accepted through `py_array_to_array_d_any_float`, so the
+/// matching binding can hand the result back in the same dtype it was given.
+#[derive(Clone, Copy)]
+pub enum InputDtype {
+    F32,
+    F64,
+}
+
 /// Converts a read-only NumPy `ndarray` (`float64`, dynamic dimension) into an owned
 /// `ndarray::ArrayD<f64>`.
 ///
-/// The input must be C-contiguous in memory. The function copies the data into a new
-/// Rust-owned buffer, preserving shape.
+/// Accepts arrays of arbitrary strides (a transpose, a slice like `a[::2]`, a broadcasted view,
+/// Fortran order, etc.): the common C-contiguous case is copied directly via a flat slice, and
+/// anything else falls back to a stride-aware view (`as_array`) copied element-by-element into a
+/// standard C-contiguous array, so the logical shape and values are preserved regardless of
+/// memory layout.
 ///
 /// # Parameters
 /// - `arr`: Read-only NumPy array view (`PyReadonlyArrayDyn<f64>`).
 ///
 /// # Returns
 /// - `Ok(ArrayD<f64>)`: Owned n-dimensional Rust array with the same shape and values.
-/// - `Err(PyValueError)`: If the input NumPy array is not contiguous, or if shape/data
-///   reconstruction fails.
+/// - `Err(PyValueError)`: If shape/data reconstruction fails.
 ///
 /// # Notes
-/// - This performs a data copy (`to_vec()`), so the returned array is independent from
-///   the original Python object.
+/// - This always performs a data copy, so the returned array is independent from the original
+///   Python object.
 pub fn py_array_to_array_d(arr: &PyReadonlyArrayDyn<'_, f64>) -> PyResult<ArrayD<f64>> {
     let shape = arr.shape().to_vec();
-    let data = arr.as_slice().map_err(|_| {
-        PyErr::new::<pyo3::exceptions::PyValueError, _>("Input array is not contiguous")
+    if let (true, Ok(data)) = (arr.is_c_contiguous(), arr.as_slice()) {
+        return ArrayD::from_shape_vec(IxDyn(&shape), data.to_vec())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()));
+    }
+    Ok(arr.as_array().to_owned())
+}
+
+/// Same as `py_array_to_array_d`, but also accepts `float32` input, so callers working with
+/// `float32` data cubes (common in large astronomical data, where upcasting to `float64` doubles
+/// memory and bandwidth) aren't forced to cast on the Python side first.
+///
+/// The sliding ops themselves are still `f64`-only (see their own modules), so a `float32` array
+/// is upcast here for the computation; the returned `InputDtype` tells `array_d_to_py_array_any_float`
+/// which dtype to hand the result back in, so the round trip preserves the caller's original dtype.
+///
+/// # Parameters
+/// - `arr`: A `numpy.ndarray` of either `float32` or `float64` dtype.
+///
+/// # Returns
+/// - `Ok((ArrayD<f64>, InputDtype))`: The array upcast to `f64`, and the dtype it came in as.
+/// - `Err(PyValueError)`: If `arr` is neither a `float32` nor a `float64` NumPy array, or if
+///   shape/data reconstruction fails.
+pub fn py_array_to_array_d_any_float(
+    arr: &Bound<'_, PyAny>,
+) -> PyResult<(ArrayD<f64>, InputDtype)> {
+    if let Ok(arr64) = arr.extract::<PyReadonlyArrayDyn<'_, f64>>() {
+        return Ok((py_array_to_array_d(&arr64)?, InputDtype::F64));
+    }
+    let arr32: PyReadonlyArrayDyn<'_, f32> = arr.extract().map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Input array must be a float32 or float64 NumPy array",
+        )
     })?;
-    ArrayD::from_shape_vec(IxDyn(&shape), data.to_vec())
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    let shape = arr32.shape().to_vec();
+    let data: Vec<f64> = match (arr32.is_c_contiguous(), arr32.as_slice()) {
+        (true, Ok(slice)) => slice.iter().map(|&v| v as f64).collect(),
+        _ => arr32.as_array().iter().map(|&v| v as f64).collect(),
+    };
+    let array = ArrayD::from_shape_vec(IxDyn(&shape), data)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    Ok((array, InputDtype::F32))
+}
+
+/// Converts a read-only boolean NumPy `ndarray` into an owned `ndarray::ArrayD<bool>`, the same
+/// way `py_array_to_array_d` does for `float64`. Used to read an optional `mask` parameter
+/// (`True` = ignore), which the sliding bindings fold into the data as NaN before computing so
+/// every core kernel's existing `is_nan` check also skips masked positions.
+///
+/// # Parameters
+/// - `arr`: Read-only NumPy array view (`PyReadonlyArrayDyn<bool>`).
+///
+/// # Returns
+/// - `Ok(ArrayD<bool>)`: Owned n-dimensional Rust array with the same shape and values.
+/// - `Err(PyValueError)`: If shape/data reconstruction fails.
+pub fn py_mask_to_array_d(arr: &PyReadonlyArrayDyn<'_, bool>) -> PyResult<ArrayD<bool>> {
+    let shape = arr.shape().to_vec();
+    if let (true, Ok(data)) = (arr.is_c_contiguous(), arr.as_slice()) {
+        return ArrayD::from_shape_vec(IxDyn(&shape), data.to_vec())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()));
+    }
+    Ok(arr.as_array().to_owned())
+}
+
+/// Applies an optional `numpy.ma`-style mask (`True` = ignore) to `data` in place, overwriting
+/// masked positions with NaN so the existing NaN-skip branch in every sliding op also skips them.
+///
+/// # Returns
+/// - `Err(PyValueError)` if `mask`'s shape doesn't match `data`'s.
+pub fn apply_mask(data: &mut ArrayD<f64>, mask: &ArrayD<bool>) -> PyResult<()> {
+    if mask.shape() != data.shape() {
+        let args = format!(
+            "mask shape {:?} does not match data shape {:?}",
+            mask.shape(),
+            data.shape()
+        );
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(args));
+    }
+    ndarray::Zip::from(data).and(mask).for_each(|d, &m| {
+        if m {
+            *d = f64::NAN;
+        }
+    });
+    Ok(())
+}
+
+/// Converts an owned `f64` result back to a NumPy array of `dtype` (see
+/// `py_array_to_array_d_any_float`), downcasting to `float32` when that's what the caller
+/// originally passed in.
+pub fn array_d_to_py_array_any_float<'py>(
+    py: Python<'py>,
+    arr: ArrayD<f64>,
+    dtype: InputDtype,
+) -> PyResult<Bound<'py, PyAny>> {
+    match dtype {
+        InputDtype::F64 => Ok(array_d_to_py_array(py, arr)?.into_any()),
+        InputDtype::F32 => Ok(array_d_to_py_array(py, arr.mapv(|v| v as f32))?.into_any()),
+    }
 }
 
 /// Converts an owned `ndarray::ArrayD<T>` into a NumPy dynamic-dimensional array
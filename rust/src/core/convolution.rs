@@ -1,77 +1,228 @@
-// ! N-dimensional convolution operation.
+//! N-dimensional convolution operation.
 
-use ndarray::ArrayViewMutD;
+use ndarray::{ArrayD, ArrayViewD, ArrayViewMutD, Axis, IxDyn, Zip};
+use rayon::prelude::*;
 
 // local
 use crate::core::padding::SlidingWorkspace;
-
-// todo need to see if the results are right.
+use crate::core::utils::neumaier_add;
 
 /// N-dimensional convolution for a kernel with weights and an input array with NaNs.
 /// The NaN values in the input are ignored in the convolution operation.
 /// If no valid values in the kernel window, the output is set to NaN.
-pub fn convolution<'a>(padded: &mut SlidingWorkspace, mut data: ArrayViewMutD<'a, f64>) {
-    padded.idx.fill(0);
-
-    let has_nan = data.iter().any(|v| v.is_nan());
-    let mut base = 0isize;
-    let mut out_linear = 0usize;
-    let padded_strides = padded.padded_buffer.strides();
-    let padded_slice = padded
-        .padded_buffer
+/// `neumaier` selects Neumaier-compensated summation, trading a bit of speed for numerical
+/// stability on large kernels or wide-magnitude data (see `sliding_mean` for the same knob).
+/// `allow_separable` opts into the rank-1 separable fast path (see `try_separable_factors`):
+/// when the kernel factors, `n` cheap 1D passes replace the dense `O(prod k_d)` loop. This is
+/// only exactly equivalent to the dense path when the input has no NaNs (a NaN masked out in one
+/// 1D pass affects every later pass differently than masking it once in the full window), so
+/// callers with NaN-bearing data must knowingly opt in.
+pub fn convolution<'a>(
+    workspace: &SlidingWorkspace,
+    data: ArrayViewMutD<'a, f64>,
+    neumaier: bool,
+    allow_separable: bool,
+) {
+    if allow_separable {
+        if let Some(factors) = try_separable_factors(&workspace.kernel) {
+            separable_convolution(workspace, &factors, data, neumaier);
+            return;
+        }
+    }
+
+    dense_convolution(workspace, data, neumaier);
+}
+
+/// The original dense path: every output element visits every (nonzero) kernel element.
+fn dense_convolution<'a>(
+    workspace: &SlidingWorkspace,
+    mut data: ArrayViewMutD<'a, f64>,
+    neumaier: bool,
+) {
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
         .as_slice_memory_order()
         .expect("Padding buffer must be contiguous");
+    let has_nan = padded_slice.iter().any(|v| v.is_nan());
     let out_slice = data
         .as_slice_memory_order_mut()
         .expect("Output view must be contiguous");
 
-    loop {
-        let mut has_valid = false;
-        let mut acc = 0.0;
-
-        if has_nan {
-            for i in 0..padded.kernel_offsets.len() {
-                let v = unsafe {
-                    *padded_slice
-                        .as_ptr()
-                        .offset(base + padded.kernel_offsets[i])
-                };
-                if !v.is_nan() {
-                    acc += v * padded.kernel_weights[i];
-                    has_valid = true;
+    let k_offsets = &workspace.kernel_offsets;
+    let k_weights = &workspace.kernel_weights;
+
+    out_slice
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(out_linear, out)| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+
+            let mut has_valid = false;
+            let mut acc = 0.0;
+            let mut c = 0.0;
+
+            for i in 0..k_offsets.len() {
+                let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+                if has_nan && v.is_nan() {
+                    continue;
+                }
+                has_valid = true;
+                if neumaier {
+                    neumaier_add(&mut acc, &mut c, v * k_weights[i]);
+                } else {
+                    acc += v * k_weights[i];
                 }
             }
-        } else {
-            for i in 0..padded.kernel_offsets.len() {
-                let v = unsafe {
-                    *padded_slice
-                        .as_ptr()
-                        .offset(base + padded.kernel_offsets[i])
-                };
-                acc += v * padded.kernel_weights[i];
-            }
-        }
 
-        out_slice[out_linear] = if !has_valid { f64::NAN } else { acc };
-        out_linear += 1;
+            *out = if !has_valid { f64::NAN } else { acc + c };
+        });
+}
 
-        // increment output index
-        let mut d = padded.ndim;
-        loop {
-            if d == 0 {
-                return;
-            }
-            d -= 1;
+/// Runs the separable fast path: one 1D convolution per axis, each collapsing that axis from its
+/// padded length down to `workspace.out_shape[axis]`, reusing the previous pass's output as the
+/// next pass's input.
+fn separable_convolution<'a>(
+    workspace: &SlidingWorkspace,
+    factors: &[Vec<f64>],
+    mut data: ArrayViewMutD<'a, f64>,
+    neumaier: bool,
+) {
+    let mut current = workspace.padded.clone();
+    for axis in 0..workspace.ndim {
+        current = apply_1d_axis(
+            current.view(),
+            axis,
+            &factors[axis],
+            workspace.stride[axis],
+            workspace.dilation[axis],
+            workspace.out_shape[axis],
+            neumaier,
+        );
+    }
+    data.assign(&current);
+}
+
+/// Convolves every lane along `axis` with the 1D kernel `factor`, collapsing that axis from its
+/// current length down to `out_len` (per `stride`/`dilation`). NaNs are ignored the same way the
+/// dense path ignores them, just one axis at a time.
+pub(crate) fn apply_1d_axis(
+    input: ArrayViewD<f64>,
+    axis: usize,
+    factor: &[f64],
+    stride: usize,
+    dilation: usize,
+    out_len: usize,
+    neumaier: bool,
+) -> ArrayD<f64> {
+    let mut out_shape = input.shape().to_vec();
+    out_shape[axis] = out_len;
+    let mut out = ArrayD::<f64>::zeros(IxDyn(&out_shape));
+    let ax = Axis(axis);
 
-            padded.idx[d] += 1;
-            base += padded_strides[d];
+    Zip::from(out.lanes_mut(ax))
+        .and(input.lanes(ax))
+        .for_each(|mut out_lane, in_lane| {
+            for o in 0..out_len {
+                let mut has_valid = false;
+                let mut acc = 0.0;
+                let mut c = 0.0;
+
+                for (t, &w) in factor.iter().enumerate() {
+                    let v = in_lane[o * stride + t * dilation];
+                    if v.is_nan() {
+                        continue;
+                    }
+                    has_valid = true;
+                    if neumaier {
+                        neumaier_add(&mut acc, &mut c, v * w);
+                    } else {
+                        acc += v * w;
+                    }
+                }
 
-            if padded.idx[d] < padded.out_shape[d] {
-                break;
+                out_lane[o] = if !has_valid { f64::NAN } else { acc + c };
             }
+        });
+
+    out
+}
+
+/// Attempts to factor `kernel` into `ndim` 1D vectors whose outer product reproduces it (i.e. a
+/// rank-1 separable kernel), within a relative tolerance. Finds the max-abs entry `K[p]` as a
+/// well-conditioned pivot, reads off candidate factors `f_d[i] = K[p with axis d set to i] / K[p]`
+/// along each axis through it (scaling `f_0` by `K[p]` so the outer product's magnitude matches),
+/// then verifies `outer_product(f_0, ..., f_{n-1}) ≈ K` everywhere. Returns `None` (and the caller
+/// falls back to the dense path) for an all-zero kernel or one that isn't separable.
+pub(crate) fn try_separable_factors(kernel: &ArrayD<f64>) -> Option<Vec<Vec<f64>>> {
+    let ndim = kernel.ndim();
+    let shape = kernel.shape().to_vec();
+    let strides = kernel.strides().to_vec();
+    let slice = kernel.as_slice_memory_order()?;
+    let offset_of = |idx: &[usize]| -> usize {
+        idx.iter()
+            .zip(&strides)
+            .map(|(&i, &s)| i as isize * s)
+            .sum::<isize>() as usize
+    };
+
+    let mut idx = vec![0usize; ndim];
+    let mut pivot_idx = idx.clone();
+    let mut pivot_val = 0.0f64;
+    loop {
+        let v = slice[offset_of(&idx)];
+        if v.abs() > pivot_val.abs() {
+            pivot_val = v;
+            pivot_idx = idx.clone();
+        }
+        if !increment_index(&mut idx, &shape) {
+            break;
+        }
+    }
+    if pivot_val == 0.0 {
+        return None;
+    }
+
+    let mut factors: Vec<Vec<f64>> = Vec::with_capacity(ndim);
+    for d in 0..ndim {
+        let mut f = Vec::with_capacity(shape[d]);
+        let mut probe = pivot_idx.clone();
+        for i in 0..shape[d] {
+            probe[d] = i;
+            f.push(slice[offset_of(&probe)] / pivot_val);
+        }
+        factors.push(f);
+    }
+    for v in factors[0].iter_mut() {
+        *v *= pivot_val;
+    }
+
+    const REL_TOL: f64 = 1e-9;
+    let mut idx = vec![0usize; ndim];
+    loop {
+        let v = slice[offset_of(&idx)];
+        let approx: f64 = (0..ndim).map(|d| factors[d][idx[d]]).product();
+        let scale = v.abs().max(pivot_val.abs()).max(1.0);
+        if (approx - v).abs() > REL_TOL * scale {
+            return None;
+        }
+        if !increment_index(&mut idx, &shape) {
+            break;
+        }
+    }
+
+    Some(factors)
+}
 
-            padded.idx[d] = 0;
-            base -= (padded.out_shape[d] as isize) * padded_strides[d];
+/// Odometer-style multi-index increment in row-major order, returning `false` once it wraps back
+/// to all zeros (i.e. every index has been visited).
+fn increment_index(idx: &mut [usize], shape: &[usize]) -> bool {
+    for d in (0..idx.len()).rev() {
+        idx[d] += 1;
+        if idx[d] < shape[d] {
+            return true;
         }
+        idx[d] = 0;
     }
+    false
 }
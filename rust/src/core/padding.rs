@@ -2,16 +2,45 @@
 //! This struct does the padding and keeps the padded buffer and offsets needed for all sliding
 //! operations.
 
-use ndarray::{ArrayD, ArrayViewD, Axis, IxDyn, Slice};
+use ndarray::{ArrayD, ArrayViewD, Axis, IxDyn, Slice, Zip};
+
+/// The statistic computed per lane by `PaddingMode::Statistic`, mirroring numpy.pad's
+/// `'mean'`/`'maximum'`/`'minimum'` modes (over the whole axis, i.e. no `stat_length` cutoff).
+#[derive(Clone, Copy)]
+pub enum StatisticKind {
+    Mean,
+    Maximum,
+    Minimum,
+}
 
 /// The different padding modes implemented in the SlidingWorkspace struct.
 /// Constant puts constant values as the padding.
-/// Reflect, reflects the values at the border.
+/// Reflect, reflects the values at the border (edge sample not repeated).
 /// Replicate, replicates the border values.
+/// Wrap, wraps the values around (periodic/circular padding).
+/// Symmetric, reflects the values at the border, repeating the edge sample (unlike Reflect).
+/// Statistic, fills each halo lane with the mean/max/min of the corresponding core lane.
 pub enum PaddingMode {
     Constant(f64),
     Reflect,
     Replicate,
+    Wrap,
+    Symmetric,
+    Statistic(StatisticKind),
+}
+
+/// Which convolution boundary convention drives the padding widths, mirroring NumPy/SciPy's
+/// `'valid'`/`'same'`/`'full'`.
+/// Valid applies no padding at all (the output shrinks by `(k - 1) * dilation` per axis).
+/// Same pads just enough that, at stride 1, the output length matches the input length; padding
+/// is split as evenly as possible with the extra element (if any) going to the high side.
+/// Full pads by `(k - 1) * dilation` on both sides, so every kernel position overlapping the
+/// input by at least one sample is included.
+#[derive(Clone, Copy)]
+pub enum ConvMode {
+    Valid,
+    Same,
+    Full,
 }
 
 /// Workspace used in all sliding operations.
@@ -21,16 +50,21 @@ pub struct SlidingWorkspace {
     pub padded: ArrayD<f64>,        // reused by padding operations
     pub kernel_offsets: Vec<isize>, // the offsets of the kernel elements in the padded buffer
     pub kernel_weights: Vec<f64>,   // the weights of the kernel
-    ndim: usize,                    // number of dimensions
-    pad: Vec<usize>,                // per-dimension padding
+    pub kernel_shape: Vec<usize>,   // shape of the kernel (needed by per-axis algorithms)
+    pub ndim: usize,                // number of dimensions
+    pub pad_lo: Vec<usize>,         // per-dimension low-side (left) padding
+    pub pad_hi: Vec<usize>,         // per-dimension high-side (right) padding
     padding_mode: PaddingMode,      // padding mode
-    out_shape: Vec<usize>,          // shape of the output
-    kernel: ArrayD<f64>,            // the actual kernel
+    pub input_shape: Vec<usize>,    // literal shape of the data handed to pad_input
+    pub out_shape: Vec<usize>,      // shape of the output (post-stride)
+    pub stride: Vec<usize>,         // per-dimension output stride (downsampling)
+    pub dilation: Vec<usize>,       // per-dimension kernel tap spacing (atrous/dilated)
+    pub kernel: ArrayD<f64>,        // the actual (broadcast) kernel, aligned with kernel_shape
     filled: bool, // used when the padding is set to constant (so the padding is only done once)
 }
 
 impl SlidingWorkspace {
-    /// Creates a new SlidingWorkspace.
+    /// Creates a new SlidingWorkspace with unit stride and dilation.
     /// Computes the offsets needed during the multithreaded sliding operations.
     /// Also creates the padded data (use the pad_input method to populate the padded buffer and
     /// compute the padding (if needed).
@@ -41,6 +75,7 @@ impl SlidingWorkspace {
         padding_mode: PaddingMode,
     ) -> Result<Self, String> {
         let ndim = input_shape.len();
+        let kernel = Self::broadcast_kernel(kernel, ndim)?;
         let pad: Vec<usize> = kernel.shape().iter().map(|&k| k / 2).collect();
         let padded_shape = IxDyn(
             input_shape
@@ -51,6 +86,7 @@ impl SlidingWorkspace {
                 .as_slice(),
         );
         let out_shape = input_shape.to_vec();
+        let kernel_shape = kernel.shape().to_vec();
 
         // kernel offsets and weights (skip zeros)
         let kernel_offsets = Vec::with_capacity(kernel.len());
@@ -58,11 +94,16 @@ impl SlidingWorkspace {
 
         let mut instance = SlidingWorkspace {
             ndim,
-            pad,
+            pad_lo: pad.clone(),
+            pad_hi: pad,
             padding_mode,
             padded: ArrayD::zeros(padded_shape),
+            input_shape: input_shape.to_vec(),
             out_shape,
+            stride: vec![1; ndim],
+            dilation: vec![1; ndim],
             kernel,
+            kernel_shape,
             kernel_offsets,
             kernel_weights,
             filled: false,
@@ -71,6 +112,169 @@ impl SlidingWorkspace {
         Ok(instance)
     }
 
+    /// Re-derives padding, output shape, and kernel offsets for a strided and/or dilated (atrous)
+    /// operation: `stride[d]` downsamples the output along axis `d`, `dilation[d]` spaces kernel
+    /// taps apart along axis `d`. Must be called right after `new` (before `pad_input`), since it
+    /// resizes the padded buffer and invalidates any padding already filled.
+    pub fn with_stride_dilation(
+        mut self,
+        stride: Vec<usize>,
+        dilation: Vec<usize>,
+    ) -> Result<Self, String> {
+        if stride.len() != self.ndim || dilation.len() != self.ndim {
+            return Err(format!(
+                "stride and dilation must have {} entries (one per dimension), got {} and {}.",
+                self.ndim,
+                stride.len(),
+                dilation.len()
+            ));
+        }
+
+        // Total padding needed is `dilation * (k - 1)`, split low/high by splitting `k - 1` and
+        // then scaling by `dilation`. For an odd kernel `k - 1` is even and splits evenly (same
+        // result as the old symmetric `d * (k / 2)` formula); for an even kernel it's odd, so the
+        // extra `dilation` goes to the high side instead of rounding both sides up and overshooting
+        // `out_shape` by one.
+        let pad_lo: Vec<usize> = self
+            .kernel_shape
+            .iter()
+            .zip(&dilation)
+            .map(|(&k, &d)| d * (k.saturating_sub(1) / 2))
+            .collect();
+        let pad_hi: Vec<usize> = self
+            .kernel_shape
+            .iter()
+            .zip(&dilation)
+            .map(|(&k, &d)| {
+                let total = k.saturating_sub(1);
+                d * (total - total / 2)
+            })
+            .collect();
+        let out_shape: Vec<usize> = self
+            .input_shape
+            .iter()
+            .enumerate()
+            .map(|(d, &n)| {
+                let k = self.kernel_shape[d];
+                let total = n + pad_lo[d] + pad_hi[d] - dilation[d] * (k.saturating_sub(1)) - 1;
+                total / stride[d] + 1
+            })
+            .collect();
+        let padded_shape = IxDyn(
+            self.input_shape
+                .iter()
+                .enumerate()
+                .map(|(d, &n)| n + pad_lo[d] + pad_hi[d])
+                .collect::<Vec<_>>()
+                .as_slice(),
+        );
+
+        self.pad_lo = pad_lo;
+        self.pad_hi = pad_hi;
+        self.out_shape = out_shape;
+        self.stride = stride;
+        self.dilation = dilation;
+        self.padded = ArrayD::zeros(padded_shape);
+        self.filled = false;
+        self.kernel_offsets.clear();
+        self.kernel_weights.clear();
+        self.create_offsets();
+        Ok(self)
+    }
+
+    /// Re-derives asymmetric padding, output shape, and kernel offsets for a given convolution
+    /// boundary convention (`ConvMode::Valid`/`Same`/`Full`), combined with per-axis stride and
+    /// dilation. Unlike `with_stride_dilation`, the low-side and high-side padding widths may
+    /// differ (needed for even-length kernels and for NumPy/SciPy-style `'same'` output). Must be
+    /// called right after `new` (before `pad_input`).
+    pub fn with_conv_mode(
+        mut self,
+        conv_mode: ConvMode,
+        stride: Vec<usize>,
+        dilation: Vec<usize>,
+    ) -> Result<Self, String> {
+        if stride.len() != self.ndim || dilation.len() != self.ndim {
+            return Err(format!(
+                "stride and dilation must have {} entries (one per dimension), got {} and {}.",
+                self.ndim,
+                stride.len(),
+                dilation.len()
+            ));
+        }
+
+        let mut pad_lo = vec![0usize; self.ndim];
+        let mut pad_hi = vec![0usize; self.ndim];
+        for d in 0..self.ndim {
+            let n = self.input_shape[d];
+            let k = self.kernel_shape[d];
+            let s = stride[d];
+            let dil = dilation[d];
+            let (lo, hi) = match conv_mode {
+                ConvMode::Valid => (0, 0),
+                ConvMode::Full => {
+                    let total = dil * k.saturating_sub(1);
+                    (total, total)
+                }
+                ConvMode::Same => {
+                    let out_len = (n + s - 1) / s; // ceil(n / s)
+                    let total = (out_len.saturating_sub(1) * s + dil * k.saturating_sub(1) + 1)
+                        .saturating_sub(n);
+                    let lo = total / 2;
+                    (lo, total - lo)
+                }
+            };
+            pad_lo[d] = lo;
+            pad_hi[d] = hi;
+        }
+
+        let out_shape: Vec<usize> = (0..self.ndim)
+            .map(|d| {
+                let n = self.input_shape[d];
+                let k = self.kernel_shape[d];
+                let total = n + pad_lo[d] + pad_hi[d] - dilation[d] * k.saturating_sub(1) - 1;
+                total / stride[d] + 1
+            })
+            .collect();
+        let padded_shape = IxDyn(
+            (0..self.ndim)
+                .map(|d| self.input_shape[d] + pad_lo[d] + pad_hi[d])
+                .collect::<Vec<_>>()
+                .as_slice(),
+        );
+
+        self.pad_lo = pad_lo;
+        self.pad_hi = pad_hi;
+        self.out_shape = out_shape;
+        self.stride = stride;
+        self.dilation = dilation;
+        self.padded = ArrayD::zeros(padded_shape);
+        self.filled = false;
+        self.kernel_offsets.clear();
+        self.kernel_weights.clear();
+        self.create_offsets();
+        Ok(self)
+    }
+
+    /// Broadcasts `kernel` up to `ndim` dimensions so a lower-rank kernel can be applied across a
+    /// chosen subset of the data's trailing axes (e.g. a 1-D kernel smoothing only the last axis
+    /// of a 3-D cube, or a 2-D spatial kernel run over a stack of images).
+    /// A size-1 kernel axis (whether present in the input or inserted here) means "window of 1",
+    /// i.e. no sliding along that axis; this is resolved once, here, into the kernel shape used by
+    /// `create_offsets` so there is no per-element broadcasting cost.
+    fn broadcast_kernel(mut kernel: ArrayD<f64>, ndim: usize) -> Result<ArrayD<f64>, String> {
+        if kernel.ndim() > ndim {
+            return Err(format!(
+                "Kernel has {} dimensions, which is more than data's {} dimensions.",
+                kernel.ndim(),
+                ndim
+            ));
+        }
+        while kernel.ndim() < ndim {
+            kernel = kernel.insert_axis(Axis(0));
+        }
+        Ok(kernel)
+    }
+
     /// Pad the input data into the padded buffer.
     /// No shape checks are done so make sure that the input data shape matches the valid shape
     /// (and not the padded shape), i.e. must match 'input_shape' given in new().
@@ -88,9 +292,9 @@ impl SlidingWorkspace {
 
         // populate input inside the padded buffer
         let mut window = self.padded.view_mut();
-        for (axis, p) in self.pad.iter().enumerate() {
+        for (axis, p) in self.pad_lo.iter().enumerate() {
             let start = *p as isize;
-            let end = start + self.out_shape[axis] as isize;
+            let end = start + self.input_shape[axis] as isize;
             window = window.slice_axis_move(Axis(axis), Slice::from(start..end));
         }
         window.assign(&input);
@@ -100,6 +304,9 @@ impl SlidingWorkspace {
             PaddingMode::Constant(_) => (), // already done
             PaddingMode::Reflect => self.fill_reflect(),
             PaddingMode::Replicate => self.fill_replicate(),
+            PaddingMode::Wrap => self.fill_wrap(),
+            PaddingMode::Symmetric => self.fill_symmetric(),
+            PaddingMode::Statistic(stat) => self.fill_statistic(stat),
         }
     }
 
@@ -117,7 +324,7 @@ impl SlidingWorkspace {
             let dim = out_shape[d];
             let idx = linear % dim;
             linear /= dim;
-            base += (idx as isize) * padded_strides[d];
+            base += (idx as isize) * (self.stride[d] as isize) * padded_strides[d];
         }
         base
     }
@@ -125,26 +332,27 @@ impl SlidingWorkspace {
     /// Does the reflect mode padding.
     fn fill_reflect(&mut self) {
         for axis_idx in 0..self.ndim {
-            let pad = self.pad[axis_idx];
-            if pad == 0 {
+            let pad_lo = self.pad_lo[axis_idx];
+            let pad_hi = self.pad_hi[axis_idx];
+            if pad_lo == 0 && pad_hi == 0 {
                 continue;
             }
 
-            let core_len = self.out_shape[axis_idx];
+            let core_len = self.input_shape[axis_idx];
             let axis = Axis(axis_idx);
             let padded = self.padded.view_mut();
-            let (mut left_pad, tail) = padded.split_at(axis, pad);
+            let (mut left_pad, tail) = padded.split_at(axis, pad_lo);
             let (core, mut right_pad) = tail.split_at(axis, core_len);
 
-            for offset in 0..pad {
+            for offset in 0..pad_lo {
                 let src_idx = Self::even_mirror_index(offset, core_len);
-                let dst_idx = pad - 1 - offset;
+                let dst_idx = pad_lo - 1 - offset;
                 let src = core.index_axis(axis, src_idx);
                 let mut dst = left_pad.index_axis_mut(axis, dst_idx);
                 dst.assign(&src);
             }
 
-            for offset in 0..pad {
+            for offset in 0..pad_hi {
                 let src_idx = core_len - 1 - Self::even_mirror_index(offset, core_len);
                 let dst_idx = offset;
                 let src = core.index_axis(axis, src_idx);
@@ -171,32 +379,150 @@ impl SlidingWorkspace {
     /// Does the replicate mode padding.
     fn fill_replicate(&mut self) {
         for axis_idx in 0..self.ndim {
-            let pad = self.pad[axis_idx];
-            if pad == 0 {
+            let pad_lo = self.pad_lo[axis_idx];
+            let pad_hi = self.pad_hi[axis_idx];
+            if pad_lo == 0 && pad_hi == 0 {
                 continue;
             }
 
-            let core_len = self.out_shape[axis_idx];
+            let core_len = self.input_shape[axis_idx];
             let axis = Axis(axis_idx);
             let padded = self.padded.view_mut();
-            let (mut left_pad, tail) = padded.split_at(axis, pad);
+            let (mut left_pad, tail) = padded.split_at(axis, pad_lo);
             let (core, mut right_pad) = tail.split_at(axis, core_len);
 
             let left_edge = core.index_axis(axis, 0);
             let right_edge = core.index_axis(axis, core_len - 1);
 
-            for i in 0..pad {
+            for i in 0..pad_lo {
                 let mut dst = left_pad.index_axis_mut(axis, i);
                 dst.assign(&left_edge);
             }
 
-            for i in 0..pad {
+            for i in 0..pad_hi {
                 let mut dst = right_pad.index_axis_mut(axis, i);
                 dst.assign(&right_edge);
             }
         }
     }
 
+    /// Does the wrap (periodic/circular) mode padding: the halo continues the data as if it
+    /// repeated indefinitely, so the index wraps around modulo the axis length.
+    fn fill_wrap(&mut self) {
+        for axis_idx in 0..self.ndim {
+            let pad_lo = self.pad_lo[axis_idx];
+            let pad_hi = self.pad_hi[axis_idx];
+            if pad_lo == 0 && pad_hi == 0 {
+                continue;
+            }
+
+            let core_len = self.input_shape[axis_idx];
+            let axis = Axis(axis_idx);
+            let padded = self.padded.view_mut();
+            let (mut left_pad, tail) = padded.split_at(axis, pad_lo);
+            let (core, mut right_pad) = tail.split_at(axis, core_len);
+
+            for dst_idx in 0..pad_lo {
+                let src_idx = (core_len as isize - pad_lo as isize + dst_idx as isize)
+                    .rem_euclid(core_len as isize) as usize;
+                let src = core.index_axis(axis, src_idx);
+                let mut dst = left_pad.index_axis_mut(axis, dst_idx);
+                dst.assign(&src);
+            }
+
+            for dst_idx in 0..pad_hi {
+                let src_idx = dst_idx % core_len;
+                let src = core.index_axis(axis, src_idx);
+                let mut dst = right_pad.index_axis_mut(axis, dst_idx);
+                dst.assign(&src);
+            }
+        }
+    }
+
+    /// Does the symmetric mode padding: reflects at the border like `Reflect`, but repeats the
+    /// edge sample instead of skipping it (matching NumPy's `np.pad(mode="symmetric")`).
+    fn fill_symmetric(&mut self) {
+        for axis_idx in 0..self.ndim {
+            let pad_lo = self.pad_lo[axis_idx];
+            let pad_hi = self.pad_hi[axis_idx];
+            if pad_lo == 0 && pad_hi == 0 {
+                continue;
+            }
+
+            let core_len = self.input_shape[axis_idx];
+            let axis = Axis(axis_idx);
+            let padded = self.padded.view_mut();
+            let (mut left_pad, tail) = padded.split_at(axis, pad_lo);
+            let (core, mut right_pad) = tail.split_at(axis, core_len);
+
+            for offset in 0..pad_lo {
+                let src_idx = Self::odd_mirror_index(offset, core_len);
+                let dst_idx = pad_lo - 1 - offset;
+                let src = core.index_axis(axis, src_idx);
+                let mut dst = left_pad.index_axis_mut(axis, dst_idx);
+                dst.assign(&src);
+            }
+
+            for offset in 0..pad_hi {
+                let src_idx = core_len - 1 - Self::odd_mirror_index(offset, core_len);
+                let dst_idx = offset;
+                let src = core.index_axis(axis, src_idx);
+                let mut dst = right_pad.index_axis_mut(axis, dst_idx);
+                dst.assign(&src);
+            }
+        }
+    }
+
+    /// Mirrors the index for symmetric (edge-repeating) reflection padding.
+    #[inline]
+    fn odd_mirror_index(distance: usize, len: usize) -> usize {
+        if len == 0 {
+            return 0;
+        }
+        let period = 2 * len;
+        let mut d = distance % period;
+        if d >= len {
+            d = period - 1 - d;
+        }
+        d
+    }
+
+    /// Does the statistic mode padding: each halo lane is filled with the mean/max/min of the
+    /// corresponding core lane (numpy.pad's `'mean'`/`'maximum'`/`'minimum'`, unbounded
+    /// `stat_length`).
+    fn fill_statistic(&mut self, stat: StatisticKind) {
+        for axis_idx in 0..self.ndim {
+            let pad_lo = self.pad_lo[axis_idx];
+            let pad_hi = self.pad_hi[axis_idx];
+            if pad_lo == 0 && pad_hi == 0 {
+                continue;
+            }
+
+            let core_len = self.input_shape[axis_idx];
+            let axis = Axis(axis_idx);
+            let padded = self.padded.view_mut();
+            let (mut left_pad, tail) = padded.split_at(axis, pad_lo);
+            let (core, mut right_pad) = tail.split_at(axis, core_len);
+
+            Zip::from(core.lanes(axis))
+                .and(left_pad.lanes_mut(axis))
+                .and(right_pad.lanes_mut(axis))
+                .for_each(|core_lane, mut left_lane, mut right_lane| {
+                    let value = match stat {
+                        StatisticKind::Mean => core_lane.sum() / core_lane.len() as f64,
+                        StatisticKind::Maximum => {
+                            core_lane.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+                        }
+                        StatisticKind::Minimum => {
+                            core_lane.iter().cloned().fold(f64::INFINITY, f64::min)
+                        }
+                    };
+                    left_lane.fill(value);
+                    right_lane.fill(value);
+                });
+        }
+    }
+
     /// Creates the kernel offsets and weights for the sliding operation.
     fn create_offsets(&mut self) {
         let mut idx = vec![0usize; self.ndim];
@@ -214,7 +540,7 @@ impl SlidingWorkspace {
                 // padded offset
                 let mut offset = 0isize;
                 for d in 0..self.ndim {
-                    offset += (idx[d] as isize) * padded_strides[d];
+                    offset += (idx[d] as isize) * (self.dilation[d] as isize) * padded_strides[d];
                 }
                 self.kernel_offsets.push(offset);
                 self.kernel_weights.push(weight);
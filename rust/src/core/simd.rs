@@ -0,0 +1,189 @@
+//! Runtime-dispatched SIMD kernels for the hot `acc += v * w` accumulation loop shared by the
+//! sliding reductions (currently `sliding_mean`).
+//!
+//! Mirrors the approach NumPy uses in its `loops_arithm_fp.dispatch` units: several copies of the
+//! same inner loop are compiled for different CPU feature levels, and the fastest one the running
+//! CPU actually supports is picked once (via `is_x86_feature_detected!`) and cached.
+
+use std::sync::OnceLock;
+
+/// Signature shared by every accumulation kernel: given a pointer to the first padded value of a
+/// contiguous run and the matching kernel weights, returns `(sum(v * w), sum(w))`.
+type AccumulateFn = unsafe fn(*const f64, &[f64]) -> (f64, f64);
+
+static DISPATCH: OnceLock<AccumulateFn> = OnceLock::new();
+
+/// Accumulates a contiguous run of `weights.len()` padded values starting at `ptr`, dispatching to
+/// the widest SIMD kernel the current CPU supports. Safety: `ptr` must be valid for
+/// `weights.len()` contiguous `f64` reads.
+#[inline]
+pub fn accumulate_contiguous(ptr: *const f64, weights: &[f64]) -> (f64, f64) {
+    let f = *DISPATCH.get_or_init(select_kernel);
+    unsafe { f(ptr, weights) }
+}
+
+fn select_kernel() -> AccumulateFn {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            return accumulate_avx512f;
+        }
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            return accumulate_avx2_fma;
+        }
+        if is_x86_feature_detected!("sse2") {
+            return accumulate_sse2;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return accumulate_neon;
+        }
+    }
+    accumulate_scalar
+}
+
+/// Portable scalar fallback, used when no wider ISA is available.
+unsafe fn accumulate_scalar(ptr: *const f64, weights: &[f64]) -> (f64, f64) {
+    let mut acc = 0.0;
+    let mut wsum = 0.0;
+    for (i, &w) in weights.iter().enumerate() {
+        let v = unsafe { *ptr.add(i) };
+        acc += v * w;
+        wsum += w;
+    }
+    (acc, wsum)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn accumulate_sse2(ptr: *const f64, weights: &[f64]) -> (f64, f64) {
+    use std::arch::x86_64::*;
+
+    let n = weights.len();
+    let lanes = n - n % 2;
+
+    let mut acc_v = _mm_setzero_pd();
+    let mut wsum_v = _mm_setzero_pd();
+    let mut i = 0;
+    while i < lanes {
+        let v = _mm_loadu_pd(ptr.add(i));
+        let w = _mm_loadu_pd(weights.as_ptr().add(i));
+        acc_v = _mm_add_pd(acc_v, _mm_mul_pd(v, w));
+        wsum_v = _mm_add_pd(wsum_v, w);
+        i += 2;
+    }
+
+    let mut acc_lanes = [0.0f64; 2];
+    let mut wsum_lanes = [0.0f64; 2];
+    _mm_storeu_pd(acc_lanes.as_mut_ptr(), acc_v);
+    _mm_storeu_pd(wsum_lanes.as_mut_ptr(), wsum_v);
+    let mut acc = acc_lanes[0] + acc_lanes[1];
+    let mut wsum = wsum_lanes[0] + wsum_lanes[1];
+
+    while i < n {
+        let v = *ptr.add(i);
+        acc += v * weights[i];
+        wsum += weights[i];
+        i += 1;
+    }
+    (acc, wsum)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn accumulate_avx2_fma(ptr: *const f64, weights: &[f64]) -> (f64, f64) {
+    use std::arch::x86_64::*;
+
+    let n = weights.len();
+    let lanes = n - n % 4;
+
+    let mut acc_v = _mm256_setzero_pd();
+    let mut wsum_v = _mm256_setzero_pd();
+    let mut i = 0;
+    while i < lanes {
+        let v = _mm256_loadu_pd(ptr.add(i));
+        let w = _mm256_loadu_pd(weights.as_ptr().add(i));
+        acc_v = _mm256_fmadd_pd(v, w, acc_v);
+        wsum_v = _mm256_add_pd(wsum_v, w);
+        i += 4;
+    }
+
+    let mut acc_lanes = [0.0f64; 4];
+    let mut wsum_lanes = [0.0f64; 4];
+    _mm256_storeu_pd(acc_lanes.as_mut_ptr(), acc_v);
+    _mm256_storeu_pd(wsum_lanes.as_mut_ptr(), wsum_v);
+    let mut acc = acc_lanes.iter().sum::<f64>();
+    let mut wsum = wsum_lanes.iter().sum::<f64>();
+
+    while i < n {
+        let v = *ptr.add(i);
+        acc += v * weights[i];
+        wsum += weights[i];
+        i += 1;
+    }
+    (acc, wsum)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn accumulate_avx512f(ptr: *const f64, weights: &[f64]) -> (f64, f64) {
+    use std::arch::x86_64::*;
+
+    let n = weights.len();
+    let lanes = n - n % 8;
+
+    let mut acc_v = _mm512_setzero_pd();
+    let mut wsum_v = _mm512_setzero_pd();
+    let mut i = 0;
+    while i < lanes {
+        let v = _mm512_loadu_pd(ptr.add(i));
+        let w = _mm512_loadu_pd(weights.as_ptr().add(i));
+        acc_v = _mm512_fmadd_pd(v, w, acc_v);
+        wsum_v = _mm512_add_pd(wsum_v, w);
+        i += 8;
+    }
+
+    let mut acc = _mm512_reduce_add_pd(acc_v);
+    let mut wsum = _mm512_reduce_add_pd(wsum_v);
+
+    while i < n {
+        let v = *ptr.add(i);
+        acc += v * weights[i];
+        wsum += weights[i];
+        i += 1;
+    }
+    (acc, wsum)
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn accumulate_neon(ptr: *const f64, weights: &[f64]) -> (f64, f64) {
+    use std::arch::aarch64::*;
+
+    let n = weights.len();
+    let lanes = n - n % 2;
+
+    let mut acc_v = vdupq_n_f64(0.0);
+    let mut wsum_v = vdupq_n_f64(0.0);
+    let mut i = 0;
+    while i < lanes {
+        let v = vld1q_f64(ptr.add(i));
+        let w = vld1q_f64(weights.as_ptr().add(i));
+        acc_v = vfmaq_f64(acc_v, v, w);
+        wsum_v = vaddq_f64(wsum_v, w);
+        i += 2;
+    }
+
+    let mut acc = vaddvq_f64(acc_v);
+    let mut wsum = vaddvq_f64(wsum_v);
+
+    while i < n {
+        let v = *ptr.add(i);
+        acc += v * weights[i];
+        wsum += weights[i];
+        i += 1;
+    }
+    (acc, wsum)
+}
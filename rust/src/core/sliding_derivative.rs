@@ -0,0 +1,114 @@
+//! N-dimensional finite-difference derivative along a chosen axis, using summation-by-parts
+//! (SBP) style stencils instead of fabricating ghost values through padding.
+//!
+//! The interior uses a diagonal stencil (e.g. the antisymmetric centered first-derivative
+//! coefficients `[-1/2, 0, 1/2]`), while the first/last few points near each edge use a small
+//! dense "block" of coefficients instead, avoiding the `O(h)` boundary error that zero/replicate
+//! padding would introduce.
+
+use ndarray::{ArrayD, ArrayViewD, Axis};
+use rayon::prelude::*;
+
+/// Whether the trailing boundary block is the leading block reused as-is (`Symmetric`) or
+/// negated (`Antisymmetric`, the usual case for a first-derivative operator).
+#[derive(Clone, Copy)]
+pub enum Symmetry {
+    Symmetric,
+    Antisymmetric,
+}
+
+/// N-dimensional SBP finite-difference derivative along `axis`.
+///
+/// `stencil` is the interior diagonal stencil (odd length, centered on the output index).
+/// `block` is a dense `block_rows x block_cols` matrix: row `i` gives the coefficients applied to
+/// the leading `block_cols` input samples to produce output point `i`. The trailing `block_rows`
+/// output points reuse the same rows in reverse order, applied to the trailing `block_cols`
+/// samples (also reversed), negated when `symmetry` is `Antisymmetric`. Every result is scaled by
+/// `1 / dx`. NaN propagates: if any stencil/block input is NaN, the output point is NaN.
+/// Parallelized with rayon over the lanes along `axis`.
+pub fn sliding_derivative(
+    data: ArrayViewD<f64>,
+    axis: usize,
+    stencil: &[f64],
+    block: &[Vec<f64>],
+    symmetry: Symmetry,
+    dx: f64,
+) -> ArrayD<f64> {
+    let mut out = ArrayD::<f64>::zeros(data.raw_dim());
+    let ax = Axis(axis);
+
+    let in_lanes: Vec<_> = data.lanes(ax).into_iter().collect();
+    let mut out_lanes: Vec<_> = out.lanes_mut(ax).into_iter().collect();
+
+    out_lanes
+        .par_iter_mut()
+        .zip(in_lanes.par_iter())
+        .for_each(|(out_lane, in_lane)| {
+            let input: Vec<f64> = in_lane.iter().copied().collect();
+            let result = apply_sbp_1d(&input, stencil, block, symmetry, dx);
+            for (o, r) in out_lane.iter_mut().zip(result) {
+                *o = r;
+            }
+        });
+
+    out
+}
+
+/// Applies the SBP stencil/block operator to a single 1D lane.
+fn apply_sbp_1d(
+    input: &[f64],
+    stencil: &[f64],
+    block: &[Vec<f64>],
+    symmetry: Symmetry,
+    dx: f64,
+) -> Vec<f64> {
+    let n = input.len();
+    let block_rows = block.len();
+    let half = stencil.len() / 2;
+    let sign = match symmetry {
+        Symmetry::Symmetric => 1.0,
+        Symmetry::Antisymmetric => -1.0,
+    };
+    let mut out = vec![0.0; n];
+
+    // Leading boundary block: row i dotted with the leading samples.
+    for (i, row) in block.iter().enumerate().take(n) {
+        out[i] = dot_checked(row, input, 0, 1, dx);
+    }
+
+    // Interior: the centered diagonal stencil, sliding with its center at the output index.
+    for i in block_rows..n.saturating_sub(block_rows) {
+        out[i] = dot_checked(stencil, input, i - half, 1, dx);
+    }
+
+    // Trailing boundary block: the same rows reversed, applied to the trailing samples
+    // (also reversed), negated for an antisymmetric (odd) operator.
+    for (d, row) in block.iter().enumerate().take(n) {
+        let i = n - 1 - d;
+        let start = n - 1;
+        let acc = dot_checked(row, input, start, -1, dx);
+        out[i] = sign * acc;
+    }
+
+    out
+}
+
+/// Dot product of `coeffs` against `input`, walking `input` from `start` in steps of `step`
+/// (`1` forward, `-1` backward), scaled by `1/dx`. Returns NaN if any visited sample is NaN or out
+/// of bounds (an undersized lane relative to the block/stencil width).
+fn dot_checked(coeffs: &[f64], input: &[f64], start: usize, step: isize, dx: f64) -> f64 {
+    let mut acc = 0.0;
+    let mut idx = start as isize;
+    for &c in coeffs {
+        if idx < 0 || idx as usize >= input.len() {
+            return f64::NAN;
+        }
+        let v = input[idx as usize];
+        if v.is_nan() {
+            return f64::NAN;
+        }
+        acc += c * v;
+        idx += step;
+    }
+    acc / dx
+}
@@ -1,262 +1,207 @@
 //! To compute the sliding mean of an n-dimensional array with possible NaN values.
 
-use ndarray::{ArrayViewD, ArrayViewMutD, IxDyn};
+use ndarray::ArrayViewMutD;
+use rayon::prelude::*;
 
 // local
-use crate::core::padding::PaddingWorkspace;
+use crate::core::convolution::{apply_1d_axis, try_separable_factors};
+use crate::core::padding::SlidingWorkspace;
+use crate::core::simd::accumulate_contiguous;
+use crate::core::utils::neumaier_add;
 
-/// N-dimensional sliding mean operation.
+/// N-dimensional sliding (weighted) mean operation.
 /// Handles NaN values by ignoring them in the mean calculation.
-pub fn slow_mean<'a>(
-    padded: &PaddingWorkspace,
+/// `neumaier` selects the Neumaier-compensated summation for better numerical stability at the
+/// cost of a bit of speed.
+/// `allow_separable` opts into the rank-1 separable fast path (see
+/// `core::convolution::try_separable_factors`): when the kernel factors and the input has no
+/// NaNs, `n` cheap 1D passes replace the dense `O(prod k_d)` loop, same invariant as
+/// `convolution`'s `allow_separable` flag (a NaN masked out in one 1D pass would propagate
+/// differently than masking it once in the full window, so the fast path is only taken when the
+/// padded buffer is NaN-free).
+///
+/// When the kernel offsets form a single contiguous run in the padded buffer (i.e. every nonzero
+/// kernel element lies along the last axis, with no gaps), the dense path's accumulation loop is
+/// routed through a
+/// runtime-dispatched SIMD kernel (see `core::simd`) instead of the scalar `acc += v * w` loop.
+pub fn sliding_mean<'a>(
+    workspace: &SlidingWorkspace,
     mut data: ArrayViewMutD<'a, f64>,
-    kernel: ArrayViewD<'a, f64>,
+    neumaier: bool,
+    allow_separable: bool,
 ) {
-    let mut padded_idx = vec![0usize; padded.ndim];
-    let kernel_raw_dim = kernel.raw_dim();
-
-    // iterate over input indices
-    for input_idx in ndarray::indices(padded.valid_shape.clone()) {
-        // count
-        let mut acc = 0.;
-        let mut weighted_sum = 0.;
-
-        // iterate over kernel
-        for k_idx in ndarray::indices(kernel_raw_dim.clone()) {
-            // compute padded index
-            for d in 0..padded.ndim {
-                padded_idx[d] = input_idx[d] + k_idx[d];
-            }
-
-            // no bounds check
-            unsafe {
-                let input_val = *padded.padded_buffer.uget(IxDyn(&padded_idx));
-                let kernel_val = *kernel.uget(k_idx);
-
-                if !input_val.is_nan() && kernel_val != 0. {
-                    // ? should I add a 0. check for kernel_val ?
-                    acc += input_val * kernel_val;
-                    weighted_sum += kernel_val;
-                }
+    if allow_separable {
+        let has_nan = workspace
+            .padded
+            .as_slice_memory_order()
+            .expect("Padding buffer must be contiguous")
+            .iter()
+            .any(|v| v.is_nan());
+        if !has_nan {
+            if let Some(factors) = try_separable_factors(&workspace.kernel) {
+                separable_mean(workspace, &factors, data, neumaier);
+                return;
             }
         }
-        // no bounds check
-        unsafe {
-            *data.uget_mut(input_idx) = if weighted_sum == 0. {
-                f64::NAN
-            } else {
-                acc / weighted_sum
-            };
-        }
     }
-}
-
-// pub fn sliding_mean<'a>(
-//     padded: &PaddingWorkspace,
-//     mut data: ArrayViewMutD<'a, f64>,
-//     kernel: ArrayViewD<'a, f64>,
-// ) {
-//     let ndim = data.ndim();
-//     let out_shape = data.shape().to_vec();
-
-//     // Fast path requires contiguous buffers.
-//     let Some(padded_slice) = padded.padded_buffer.as_slice_memory_order() else {
-//         // Fallback to current implementation if not contiguous.
-//         return slow_mean(padded, data, kernel);
-//     };
-//     let Some(out_slice) = data.as_slice_memory_order_mut() else {
-//         return slow_mean(padded, data, kernel);
-//     };
-
-//     // Strides are in elements (isize) in ndarray.
-//     let pstrides = padded.padded_buffer.strides();
-
-//     // Compute pad from kernel shape (pad = k/2).
-//     let kshape = kernel.shape();
-//     let mut pad = Vec::with_capacity(ndim);
-//     for &k in kshape {
-//         pad.push(k / 2);
-//     }
-
-//     // Precompute kernel offsets + weights (skip zeros).
-//     let mut k_offsets: Vec<isize> = Vec::with_capacity(kernel.len());
-//     let mut k_weights: Vec<f64> = Vec::with_capacity(kernel.len());
-//     for k_idx in ndarray::indices(kernel.raw_dim()) {
-//         let mut off = 0isize;
-//         for d in 0..ndim {
-//             off += (k_idx[d] as isize) * pstrides[d];
-//         }
-
-//         let w = unsafe { *kernel.uget(k_idx.clone()) };
-//         if w != 0.0 {
-//             k_offsets.push(off);
-//             k_weights.push(w);
-//         }
-//     }
-
-//     // Base offset in padded buffer for output index = 0.
-//     let mut base = 0isize;
-
-//     // Manual N-D index increment (row-major).
-//     let mut idx = vec![0usize; ndim];
-//     let mut out_linear = 0usize;
-
-//     loop {
-//         let mut acc = 0.0;
-//         let mut wsum = 0.0;
-
-//         for i in 0..k_offsets.len() {
-//             let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
-//             if !v.is_nan() {
-//                 acc += v * k_weights[i];
-//                 wsum += k_weights[i];
-//             }
-//         }
-
-//         out_slice[out_linear] = if wsum == 0.0 { f64::NAN } else { acc / wsum };
-//         out_linear += 1;
-
-//         // Increment multi-index and base offset.
-//         let mut d = ndim;
-//         loop {
-//             if d == 0 {
-//                 return;
-//             }
-//             d -= 1;
-
-//             idx[d] += 1;
-//             base += pstrides[d];
-
-//             if idx[d] < out_shape[d] {
-//                 break;
-//             }
-
-//             // Reset this dimension and carry.
-//             idx[d] = 0;
-//             base -= (out_shape[d] as isize) * pstrides[d];
-//         }
-//     }
-// }
 
+    dense_mean(workspace, data, neumaier);
+}
 
-pub fn sliding_mean<'a>(
-    padded: &PaddingWorkspace,
+/// Runs the separable fast path: a sequence of 1D passes (one per axis, via
+/// `convolution::apply_1d_axis`) accumulating the weighted sum, then a single scalar division by
+/// the kernel's total weight (the product of each 1D factor's own sum, valid because the full
+/// kernel is their outer product).
+fn separable_mean<'a>(
+    workspace: &SlidingWorkspace,
+    factors: &[Vec<f64>],
     mut data: ArrayViewMutD<'a, f64>,
-    kernel: ArrayViewD<'a, f64>,
+    neumaier: bool,
 ) {
-    let ndim = data.ndim();
-    let out_shape = data.shape().to_vec();
-    let kshape = kernel.shape().to_vec();
-    let has_nan = data.iter().any(|v| v.is_nan());
+    let total_weight: f64 = factors.iter().map(|f| f.iter().sum::<f64>()).product();
+    if total_weight == 0.0 {
+        data.fill(f64::NAN);
+        return;
+    }
 
-    let Some(padded_slice) = padded.padded_buffer.as_slice_memory_order() else {
-        return slow_mean(padded, data, kernel);
-    };
-    let Some(out_slice) = data.as_slice_memory_order_mut() else {
-        return slow_mean(padded, data, kernel);
-    };
-    let Some(kernel_slice) = kernel.as_slice_memory_order() else {
-        return slow_mean(padded, data, kernel);
-    };
+    let mut current = workspace.padded.clone();
+    for axis in 0..workspace.ndim {
+        current = apply_1d_axis(
+            current.view(),
+            axis,
+            &factors[axis],
+            workspace.stride[axis],
+            workspace.dilation[axis],
+            workspace.out_shape[axis],
+            neumaier,
+        );
+    }
+    current.mapv_inplace(|v| v / total_weight);
+    data.assign(&current);
+}
 
-    let pstrides = padded.padded_buffer.strides(); // element strides
-    let kstrides = kernel.strides();               // element strides
+/// The original dense path: every output element visits every (nonzero) kernel element.
+fn dense_mean<'a>(workspace: &SlidingWorkspace, mut data: ArrayViewMutD<'a, f64>, neumaier: bool) {
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
+    let has_nan = padded_slice.iter().any(|v| v.is_nan());
+    let out_slice = data
+        .as_slice_memory_order_mut()
+        .expect("Output view must be contiguous");
+
+    let k_offsets = &workspace.kernel_offsets;
+    let k_weights = &workspace.kernel_weights;
+
+    // A contiguous run of kernel offsets (consecutive k_offsets differ by 1) lets us gather a
+    // contiguous slice of the padded buffer and feed it straight to the SIMD accumulator, instead
+    // of following arbitrary per-element offsets. The SIMD kernel always does plain uncompensated
+    // summation (no compensated-summation variant), so it's only taken when `neumaier` is false.
+    let contiguous_run = is_contiguous_run(k_offsets);
+
+    out_slice
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(out_linear, out)| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+
+            let (acc, wsum) = if !has_nan {
+                if let (Some(start), false) = (contiguous_run, neumaier) {
+                    // Fast path: contiguous gather + SIMD FMA, NaN-free so it vectorizes cleanly.
+                    let ptr = unsafe { padded_slice.as_ptr().offset(base + start) };
+                    accumulate_contiguous(ptr, k_weights)
+                } else {
+                    accumulate_strided(padded_slice, base, k_offsets, k_weights, neumaier)
+                }
+            } else {
+                accumulate_strided_nan(padded_slice, base, k_offsets, k_weights, neumaier)
+            };
 
-    // Precompute kernel offsets + weights (skip zeros).
-    let mut k_offsets: Vec<isize> = Vec::with_capacity(kernel_slice.len());
-    let mut k_weights: Vec<f64> = Vec::with_capacity(kernel_slice.len());
+            *out = if wsum == 0.0 { f64::NAN } else { acc / wsum };
+        });
+}
 
-    // Manual multi-index over kernel
-    let mut k_idx = vec![0usize; ndim];
-    let mut k_base = 0isize;
-    loop {
-        let w = kernel_slice[k_base as usize];
-        if w != 0.0 {
-            // Convert kernel multi-index into padded offset via strides
-            let mut off = 0isize;
-            for d in 0..ndim {
-                off += (k_idx[d] as isize) * pstrides[d];
-            }
-            k_offsets.push(off);
-            k_weights.push(w);
+/// Returns the base offset of the run if `offsets` is sorted and every consecutive pair differs
+/// by exactly 1 (i.e. all kernel taps form one contiguous run along the padded buffer's last axis).
+fn is_contiguous_run(offsets: &[isize]) -> Option<isize> {
+    if offsets.is_empty() {
+        return None;
+    }
+    for pair in offsets.windows(2) {
+        if pair[1] - pair[0] != 1 {
+            return None;
         }
+    }
+    Some(offsets[0])
+}
 
-        // increment kernel index
-        let mut d = ndim;
-        loop {
-            if d == 0 {
-                break;
-            }
-            d -= 1;
-
-            k_idx[d] += 1;
-            k_base += kstrides[d];
-
-            if k_idx[d] < kshape[d] {
-                break;
-            }
-
-            k_idx[d] = 0;
-            k_base -= (kshape[d] as isize) * kstrides[d];
-            if d == 0 {
-                break;
-            }
+/// Arbitrary-stride fallback: follows `k_offsets` one at a time, no NaNs expected in the buffer.
+#[inline]
+fn accumulate_strided(
+    padded_slice: &[f64],
+    base: isize,
+    k_offsets: &[isize],
+    k_weights: &[f64],
+    neumaier: bool,
+) -> (f64, f64) {
+    if neumaier {
+        let mut acc = 0.0;
+        let mut c = 0.0;
+        let mut wsum = 0.0;
+        let mut c_w = 0.0;
+        for i in 0..k_offsets.len() {
+            let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+            neumaier_add(&mut acc, &mut c, v * k_weights[i]);
+            neumaier_add(&mut wsum, &mut c_w, k_weights[i]);
         }
-
-        if k_idx.iter().all(|&x| x == 0) {
-            // we wrapped around after finishing the last index
-            break;
+        (acc + c, wsum + c_w)
+    } else {
+        let mut acc = 0.0;
+        let mut wsum = 0.0;
+        for i in 0..k_offsets.len() {
+            let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+            acc += v * k_weights[i];
+            wsum += k_weights[i];
         }
+        (acc, wsum)
     }
+}
 
-    // Optional: skip NaN checks if there are none (extra pass)
-    
-
-    // Output loop (manual N-D)
-    let mut idx = vec![0usize; ndim];
-    let mut base = 0isize;
-    let mut out_linear = 0usize;
-
-    loop {
+/// Arbitrary-stride fallback used whenever the padded buffer holds NaN values, which rules out
+/// the dense SIMD path since every lane would need its own validity check.
+#[inline]
+fn accumulate_strided_nan(
+    padded_slice: &[f64],
+    base: isize,
+    k_offsets: &[isize],
+    k_weights: &[f64],
+    neumaier: bool,
+) -> (f64, f64) {
+    if neumaier {
         let mut acc = 0.0;
+        let mut c = 0.0;
         let mut wsum = 0.0;
-
-        if has_nan {
-            for i in 0..k_offsets.len() {
-                let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
-                if !v.is_nan() {
-                    acc += v * k_weights[i];
-                    wsum += k_weights[i];
-                }
+        let mut c_w = 0.0;
+        for i in 0..k_offsets.len() {
+            let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+            if !v.is_nan() {
+                neumaier_add(&mut acc, &mut c, v * k_weights[i]);
+                neumaier_add(&mut wsum, &mut c_w, k_weights[i]);
             }
-        } else {
-            for i in 0..k_offsets.len() {
-                let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+        }
+        (acc + c, wsum + c_w)
+    } else {
+        let mut acc = 0.0;
+        let mut wsum = 0.0;
+        for i in 0..k_offsets.len() {
+            let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+            if !v.is_nan() {
                 acc += v * k_weights[i];
                 wsum += k_weights[i];
             }
         }
-
-        out_slice[out_linear] = if wsum == 0.0 { f64::NAN } else { acc / wsum };
-        out_linear += 1;
-
-        // increment output index
-        let mut d = ndim;
-        loop {
-            if d == 0 {
-                return;
-            }
-            d -= 1;
-
-            idx[d] += 1;
-            base += pstrides[d];
-
-            if idx[d] < out_shape[d] {
-                break;
-            }
-
-            idx[d] = 0;
-            base -= (out_shape[d] as isize) * pstrides[d];
-        }
+        (acc, wsum)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,161 @@
+//! N-dimensional sliding minimum/maximum, mirroring the reduction set NumPy exposes in its
+//! `_methods` module.
+//!
+//! For a uniform rectangular (box) kernel, cost is independent of the window size thanks to the
+//! van Herk–Gil-Werman running-extremum algorithm; any other kernel (non-rectangular or
+//! non-uniform weights) falls back to a direct per-window scan over the precomputed kernel
+//! offsets.
+
+use ndarray::{ArrayD, ArrayViewMutD, Axis, Slice};
+
+// local
+use crate::core::padding::SlidingWorkspace;
+
+#[derive(Clone, Copy)]
+enum Extremum {
+    Min,
+    Max,
+}
+
+impl Extremum {
+    /// NaN is the neutral element: `combine(NaN, x) == x`, so NaNs never poison a window and only
+    /// surface in the output when every sample in it was NaN.
+    #[inline]
+    fn combine(self, a: f64, b: f64) -> f64 {
+        if a.is_nan() {
+            return b;
+        }
+        if b.is_nan() {
+            return a;
+        }
+        match self {
+            Extremum::Min => a.min(b),
+            Extremum::Max => a.max(b),
+        }
+    }
+}
+
+/// N-dimensional sliding minimum. NaN values are treated as the neutral element (ignored); a
+/// window with no valid value at all yields NaN.
+pub fn sliding_min<'a>(workspace: &SlidingWorkspace, data: ArrayViewMutD<'a, f64>) {
+    extremum(workspace, data, Extremum::Min);
+}
+
+/// N-dimensional sliding maximum. See `sliding_min` for the NaN convention.
+pub fn sliding_max<'a>(workspace: &SlidingWorkspace, data: ArrayViewMutD<'a, f64>) {
+    extremum(workspace, data, Extremum::Max);
+}
+
+fn extremum<'a>(workspace: &SlidingWorkspace, data: ArrayViewMutD<'a, f64>, which: Extremum) {
+    if is_uniform_box(workspace) {
+        van_herk_gil_werman(workspace, data, which);
+    } else {
+        direct_scan(workspace, data, which);
+    }
+}
+
+/// A kernel is a "uniform rectangular box" when every element participates (no zero-weight holes,
+/// so `kernel_offsets` covers the whole kernel shape) and every weight is equal, i.e. the kernel
+/// only encodes a window shape rather than per-sample weighting.
+fn is_uniform_box(workspace: &SlidingWorkspace) -> bool {
+    let dense = workspace.kernel_offsets.len() == workspace.kernel_shape.iter().product::<usize>();
+    if !dense {
+        return false;
+    }
+    match workspace.kernel_weights.first() {
+        Some(&w0) => workspace.kernel_weights.iter().all(|&w| w == w0),
+        None => false,
+    }
+}
+
+/// Direct O(prod(k_d)) fallback: scans every kernel offset for every output element.
+fn direct_scan<'a>(
+    workspace: &SlidingWorkspace,
+    mut data: ArrayViewMutD<'a, f64>,
+    which: Extremum,
+) {
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
+    let out_slice = data
+        .as_slice_memory_order_mut()
+        .expect("Output view must be contiguous");
+    let k_offsets = &workspace.kernel_offsets;
+
+    for (out_linear, out) in out_slice.iter_mut().enumerate() {
+        let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+        let mut acc = f64::NAN;
+        for &off in k_offsets {
+            let v = unsafe { *padded_slice.as_ptr().offset(base + off) };
+            acc = which.combine(acc, v);
+        }
+        *out = acc;
+    }
+}
+
+/// van Herk–Gil-Werman running extremum: O(N) per axis regardless of the window length `k`.
+/// Applied sequentially, one axis at a time, on a scratch copy of the padded buffer.
+fn van_herk_gil_werman<'a>(
+    workspace: &SlidingWorkspace,
+    mut data: ArrayViewMutD<'a, f64>,
+    which: Extremum,
+) {
+    let mut buffer = workspace.padded.clone();
+
+    for axis_idx in 0..workspace.ndim {
+        let k = workspace.kernel_shape[axis_idx];
+        if k <= 1 {
+            continue;
+        }
+        run_axis(&mut buffer, Axis(axis_idx), k, which);
+    }
+
+    // Each axis pass leaves the window extremum for output index `i` at lane position `i`
+    // (window [i, i + k - 1] in padded coordinates), so the result sits at the front of every
+    // axis -- crop down to the output shape rather than re-centering on `pad`.
+    let mut window = buffer.view();
+    for (axis_idx, &len) in workspace.out_shape.iter().enumerate() {
+        window = window.slice_axis_move(Axis(axis_idx), Slice::from(0..len as isize));
+    }
+    data.assign(&window);
+}
+
+/// Runs one 1-D van Herk–Gil-Werman pass of window length `k` along `axis`, in place.
+fn run_axis(buffer: &mut ArrayD<f64>, axis: Axis, k: usize, which: Extremum) {
+    let len = buffer.len_of(axis);
+    if len < k {
+        return;
+    }
+
+    let mut g = vec![0.0f64; len];
+    let mut h = vec![0.0f64; len];
+    let mut out = vec![0.0f64; len - k + 1];
+
+    for mut lane in buffer.lanes_mut(axis) {
+        // forward cumulative extremum, reset at each block boundary
+        for i in 0..len {
+            g[i] = if i % k == 0 {
+                lane[i]
+            } else {
+                which.combine(g[i - 1], lane[i])
+            };
+        }
+        // backward cumulative extremum, reset at each block boundary (scanned right to left)
+        for i in (0..len).rev() {
+            h[i] = if i == len - 1 || (i + 1) % k == 0 {
+                lane[i]
+            } else {
+                which.combine(h[i + 1], lane[i])
+            };
+        }
+        // window covering [i, i + k - 1] is combine(h[i], g[i + k - 1])
+        for i in 0..out.len() {
+            out[i] = which.combine(h[i], g[i + k - 1]);
+        }
+        for (i, &v) in out.iter().enumerate() {
+            lane[i] = v;
+        }
+    }
+}
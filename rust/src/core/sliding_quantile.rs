@@ -0,0 +1,312 @@
+//! N-dimensional sliding quantile operation for arrays with possible NaN values.
+
+use ndarray::ArrayViewMutD;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+// local
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_median::weights_all_equal;
+
+/// Select the (unweighted) `q`-quantile (`q` in `0..=1`) using partitioning, with numpy's
+/// default `'linear'` interpolation between the two bracketing order statistics (`q = 0.5` is
+/// the median).
+pub(crate) fn quantile_partition(values: &mut [f64], q: f64) -> f64 {
+    let n = values.len();
+    if n == 0 {
+        return f64::NAN;
+    }
+    if n == 1 {
+        return values[0];
+    }
+
+    let pos = q * (n - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    let frac = pos - lo as f64;
+
+    // Partially partition so that values[lo] is the element that would be there in a full sort.
+    values.select_nth_unstable_by(lo, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Less));
+    let lo_val = values[lo];
+    if hi == lo {
+        return lo_val;
+    }
+
+    // values[lo + 1..] are all >= lo_val (guaranteed by the select above), so the next order
+    // statistic can be found by a second, narrower select within that tail.
+    let target = hi - lo - 1;
+    let tail = &mut values[lo + 1..];
+    tail.select_nth_unstable_by(target, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Less));
+    let hi_val = tail[target];
+
+    lo_val + (hi_val - lo_val) * frac
+}
+
+/// Select a weighted `q`-quantile using partitioning (quickselect-style).
+/// Generalizes the "half-mass" weighted median: find m such that the cumulative weight to the
+/// left is <= `q * total_weight` and to the right is <= `(1 - q) * total_weight`.
+pub(crate) fn weighted_quantile_partition(values: &mut [f64], weights: &mut [f64], q: f64) -> f64 {
+    let n = values.len();
+    if n == 0 {
+        return f64::NAN;
+    }
+
+    let mut total_weight = 0.0;
+    for &w in weights.iter() {
+        total_weight += w;
+    }
+    if total_weight == 0.0 {
+        return f64::NAN;
+    }
+
+    let mut left = 0usize;
+    let mut right = n;
+    let mut target = q * total_weight;
+
+    loop {
+        let len = right - left;
+        if len == 0 {
+            return f64::NAN;
+        } else if len == 1 {
+            return values[left];
+        }
+
+        let pivot_index = left + len / 2;
+        let pivot_value = values[pivot_index];
+
+        // 3-way partition: [left..lt)=<pivot, [lt..gt)==pivot, [gt..right)>pivot
+        let mut lt = left;
+        let mut i = left;
+        let mut gt = right;
+
+        while i < gt {
+            let v = values[i];
+            let ord = v.partial_cmp(&pivot_value).unwrap_or(Ordering::Equal);
+            match ord {
+                Ordering::Less => {
+                    values.swap(lt, i);
+                    weights.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    gt -= 1;
+                    values.swap(i, gt);
+                    weights.swap(i, gt);
+                }
+                Ordering::Equal => {
+                    i += 1;
+                }
+            }
+        }
+
+        let mut w_left = 0.0;
+        for &w in &weights[left..lt] {
+            w_left += w;
+        }
+        let mut w_pivot = 0.0;
+        for &w in &weights[lt..gt] {
+            w_pivot += w;
+        }
+
+        if target < w_left {
+            right = lt;
+        } else if target <= w_left + w_pivot {
+            return pivot_value;
+        } else {
+            target -= w_left + w_pivot;
+            left = gt;
+        }
+    }
+}
+
+/// A single tuple of a Greenwald-Khanna (2001) epsilon-summary: `value` with a rank bracket
+/// `[rmin, rmax]` (inclusive) among all values inserted so far.
+struct GkTuple {
+    value: f64,
+    rmin: u64,
+    rmax: u64,
+}
+
+/// Inserts `v` into a sorted epsilon-summary, bracketing its rank between its predecessor's
+/// `rmin + 1` and its successor's `rmax` (or `summary.len() + 1` if it becomes the new maximum).
+/// Every tuple after it moves up one rank, so their `rmin`/`rmax` are shifted by one.
+fn gk_insert(summary: &mut Vec<GkTuple>, v: f64) {
+    let pos = summary.partition_point(|t| t.value < v);
+    let rmin = if pos == 0 {
+        1
+    } else {
+        summary[pos - 1].rmin + 1
+    };
+    let rmax = if pos == summary.len() {
+        summary.len() as u64 + 1
+    } else {
+        summary[pos].rmax
+    };
+    for t in summary[pos..].iter_mut() {
+        t.rmin += 1;
+        t.rmax += 1;
+    }
+    summary.insert(
+        pos,
+        GkTuple {
+            value: v,
+            rmin,
+            rmax,
+        },
+    );
+}
+
+/// Merges adjacent tuples whenever `rmax(next) - rmin(prev) <= floor(2*epsilon*n)`, capping the
+/// summary at `O((1/epsilon)*log(epsilon*n))` tuples. Never touches the first or last tuple so
+/// the summary's extremes stay exact.
+fn gk_compress(summary: &mut Vec<GkTuple>, epsilon: f64, n: usize) {
+    if summary.len() < 3 {
+        return;
+    }
+    let band = (2.0 * epsilon * n as f64).floor() as u64;
+    let mut i = summary.len() - 2;
+    loop {
+        if summary[i + 1].rmax.saturating_sub(summary[i - 1].rmin) <= band {
+            summary.remove(i);
+        }
+        if i == 1 {
+            break;
+        }
+        i -= 1;
+    }
+}
+
+/// Returns the first tuple whose `rmax >= ceil(q*n) + ceil(epsilon*n)`, which is guaranteed to be
+/// within `epsilon*n` ranks of the exact `q`-quantile.
+fn gk_query(summary: &[GkTuple], q: f64, n: usize, epsilon: f64) -> f64 {
+    let target = (q * n as f64).ceil() as u64 + (epsilon * n as f64).ceil() as u64;
+    for t in summary {
+        if t.rmax >= target {
+            return t.value;
+        }
+    }
+    summary.last().map_or(f64::NAN, |t| t.value)
+}
+
+/// N-dimensional sliding **approximate** quantile operation for large windows, via a
+/// Greenwald-Khanna epsilon-summary built from the (unweighted, mask-only) non-NaN values under
+/// the kernel, bounding per-window cost instead of fully sorting the window like
+/// `sliding_quantile` does. The answer for a given window is within `epsilon * n` ranks of the
+/// exact `q`-quantile, where `n` is the window's valid-sample count. Kernel entries equal to 0
+/// act as a mask; NaNs are skipped. `epsilon` trades accuracy for summary size: smaller values
+/// are more accurate but keep more tuples per window.
+pub fn sliding_quantile_approx<'a>(
+    workspace: &SlidingWorkspace,
+    mut data: ArrayViewMutD<'a, f64>,
+    q: f64,
+    epsilon: f64,
+) {
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
+    let out_slice = data
+        .as_slice_memory_order_mut()
+        .expect("Output view must be contiguous");
+
+    let k_offsets = &workspace.kernel_offsets;
+    let k_weights = &workspace.kernel_weights;
+    assert_eq!(k_offsets.len(), k_weights.len());
+
+    out_slice
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(out_linear, out)| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+
+            // Compressing only every ~1/(2*epsilon) insertions (the standard GK batch size)
+            // instead of once per value keeps `gk_insert`'s underlying Vec::insert bounded by the
+            // compressed summary size rather than letting it grow unboundedly across the window.
+            let batch_size = if epsilon > 0.0 {
+                ((1.0 / (2.0 * epsilon)).floor() as usize).max(1)
+            } else {
+                usize::MAX
+            };
+
+            let mut summary: Vec<GkTuple> = Vec::new();
+            let mut n = 0usize;
+
+            for i in 0..k_offsets.len() {
+                if k_weights[i] == 0.0 {
+                    continue;
+                }
+                let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+                if v.is_nan() {
+                    continue;
+                }
+                gk_insert(&mut summary, v);
+                n += 1;
+                if n % batch_size == 0 {
+                    gk_compress(&mut summary, epsilon, n);
+                }
+            }
+
+            *out = if n == 0 {
+                f64::NAN
+            } else {
+                gk_compress(&mut summary, epsilon, n);
+                gk_query(&summary, q, n, epsilon)
+            };
+        });
+}
+
+/// N-dimensional sliding **weighted** quantile operation.
+/// Uses kernel values as non-negative weights and ignores NaNs. Kernel entries equal to 0 act as
+/// a mask (weight 0). `q` is the target quantile in `0..=1` (e.g. `0.5` for the median, or `0.25`
+/// / `0.75` for the IQR bounds).
+pub fn sliding_quantile<'a>(
+    workspace: &SlidingWorkspace,
+    mut data: ArrayViewMutD<'a, f64>,
+    q: f64,
+) {
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
+    let out_slice = data
+        .as_slice_memory_order_mut()
+        .expect("Output view must be contiguous");
+
+    let k_offsets = &workspace.kernel_offsets;
+    let k_weights = &workspace.kernel_weights;
+    assert_eq!(k_offsets.len(), k_weights.len());
+
+    out_slice
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(out_linear, out)| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+
+            let mut window_vals = Vec::with_capacity(k_offsets.len());
+            let mut window_weights = Vec::with_capacity(k_offsets.len());
+
+            for i in 0..k_offsets.len() {
+                let w = k_weights[i];
+                if w == 0.0 {
+                    continue;
+                }
+                let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+                if v.is_nan() {
+                    continue;
+                }
+                window_vals.push(v);
+                window_weights.push(w);
+            }
+
+            *out = if window_vals.is_empty() {
+                f64::NAN
+            } else if weights_all_equal(&window_weights) {
+                quantile_partition(&mut window_vals, q)
+            } else {
+                weighted_quantile_partition(&mut window_vals, &mut window_weights, q)
+            };
+        });
+}
@@ -6,7 +6,7 @@ use rayon::prelude::*;
 // local
 use crate::core::padding::SlidingWorkspace;
 use crate::core::sliding_median::sliding_median;
-use crate::core::sliding_standard_deviation::sliding_standard_deviation;
+use crate::core::sliding_standard_deviation::{sliding_standard_deviation, VarianceDenominator};
 
 /// Gives the different mode options.
 /// Can be Mean or Median (i.e. uses the sliding mean or the sliding median).
@@ -29,6 +29,7 @@ pub fn sliding_sigma_clipping<'a>(
     sigma_lower: &Option<f64>,
     center_mode: &CenterMode,
     max_iterations: &Option<usize>,
+    neumaier: bool,
 ) -> ArrayD<bool> {
     let mut iterations: usize = 0;
     let mut mode_buffer = data.to_owned();
@@ -36,7 +37,13 @@ pub fn sliding_sigma_clipping<'a>(
 
     loop {
         // std
-        sliding_standard_deviation(padded, std_buffer.view_mut(), mode_buffer.view_mut());
+        sliding_standard_deviation(
+            padded,
+            std_buffer.view_mut(),
+            mode_buffer.view_mut(),
+            VarianceDenominator::Population,
+            neumaier,
+        );
 
         // center
         match center_mode {
@@ -0,0 +1,124 @@
+//! N-dimensional sliding skewness and excess kurtosis, computed from single-pass power sums.
+//!
+//! For each window, the non-NaN values under the (binary) kernel are folded into power sums
+//! S1=Σx, S2=Σx², S3=Σx³, S4=Σx⁴ and a valid-sample count n in one pass; the standardized third
+//! and fourth central moments are then derived from those sums, mirroring the incremental
+//! arbitrary-moment approach of the `average` crate. Unlike `sliding_mean`/
+//! `sliding_standard_deviation`, kernel entries are only used as a 0/nonzero mask here (skewness
+//! and kurtosis are defined on raw central moments, with no notion of a weighted moment).
+
+use ndarray::ArrayViewMutD;
+use rayon::prelude::*;
+
+// local
+use crate::core::padding::SlidingWorkspace;
+
+/// Per-window power sums shared by skewness and kurtosis.
+struct PowerSums {
+    n: f64,
+    s1: f64,
+    s2: f64,
+    s3: f64,
+    s4: f64,
+}
+
+/// Gathers the non-NaN, non-masked values of the window at `base` into power sums.
+fn gather_power_sums(workspace: &SlidingWorkspace, base: isize, padded_slice: &[f64]) -> PowerSums {
+    let k_offsets = &workspace.kernel_offsets;
+    let k_weights = &workspace.kernel_weights;
+
+    let mut n = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+
+    for i in 0..k_offsets.len() {
+        if k_weights[i] == 0.0 {
+            continue;
+        }
+        let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+        if v.is_nan() {
+            continue;
+        }
+        n += 1.0;
+        let v2 = v * v;
+        s1 += v;
+        s2 += v2;
+        s3 += v2 * v;
+        s4 += v2 * v2;
+    }
+
+    PowerSums { n, s1, s2, s3, s4 }
+}
+
+/// The mean `mu` and the second, third and fourth central moments `(mu, m2, m3, m4)`, with
+/// `m2 = S2/n - mu^2`; `None` for windows too small or degenerate (zero variance) to standardize
+/// higher moments against.
+fn central_moments(sums: &PowerSums) -> Option<(f64, f64, f64, f64)> {
+    if sums.n < 2.0 {
+        return None;
+    }
+    let mu = sums.s1 / sums.n;
+    let m2 = sums.s2 / sums.n - mu * mu;
+    if m2 == 0.0 {
+        return None;
+    }
+    let m3 = sums.s3 / sums.n - 3.0 * mu * sums.s2 / sums.n + 2.0 * mu.powi(3);
+    let m4 = sums.s4 / sums.n - 4.0 * mu * sums.s3 / sums.n + 6.0 * mu * mu * sums.s2 / sums.n
+        - 3.0 * mu.powi(4);
+    Some((mu, m2, m3, m4))
+}
+
+/// N-dimensional sliding skewness (standardized third central moment, `g1 = m3 / m2^1.5`).
+/// NaN values are ignored; a window with fewer than 2 valid values or zero variance yields NaN.
+pub fn sliding_skewness<'a>(workspace: &SlidingWorkspace, mut data: ArrayViewMutD<'a, f64>) {
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
+    let out_slice = data
+        .as_slice_memory_order_mut()
+        .expect("Output view must be contiguous");
+
+    out_slice
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(out_linear, out)| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+            let sums = gather_power_sums(workspace, base, padded_slice);
+
+            *out = match central_moments(&sums) {
+                Some((_, m2, m3, _)) => m3 / m2.powf(1.5),
+                None => f64::NAN,
+            };
+        });
+}
+
+/// N-dimensional sliding excess kurtosis (standardized fourth central moment minus 3,
+/// `g2 = m4 / m2^2 - 3`). NaN values are ignored; a window with fewer than 2 valid values or zero
+/// variance yields NaN.
+pub fn sliding_kurtosis<'a>(workspace: &SlidingWorkspace, mut data: ArrayViewMutD<'a, f64>) {
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
+    let out_slice = data
+        .as_slice_memory_order_mut()
+        .expect("Output view must be contiguous");
+
+    out_slice
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(out_linear, out)| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+            let sums = gather_power_sums(workspace, base, padded_slice);
+
+            *out = match central_moments(&sums) {
+                Some((_, m2, _, m4)) => m4 / (m2 * m2) - 3.0,
+                None => f64::NAN,
+            };
+        });
+}
@@ -1,149 +1,119 @@
 //! N-dimensional sliding standard deviation operation with NaN values and a weighted kernel.
-//! Also computes the sliding mean at the same time.
+//! Also computes the sliding mean at the same time, in the same pass.
 
 use ndarray::ArrayViewMutD;
 use rayon::prelude::*;
 
 // local
 use crate::core::padding::SlidingWorkspace;
-use crate::core::sliding_mean::sliding_mean;
 use crate::core::utils::neumaier_add;
 
+/// Selects the denominator used to turn the running second moment `M2` into a variance.
+pub enum VarianceDenominator {
+    /// `M2 / W`: population variance (matches the unweighted/unit-weight behavior when all
+    /// kernel weights are equal).
+    Population,
+    /// `M2 / (W - sum_w2 / W)`: reliability-weighted sample variance, appropriate when the
+    /// kernel weights represent per-sample reliabilities rather than repeat counts.
+    ReliabilitySample,
+    /// `M2 / (n - ddof)`, where `n` is the plain count of valid (non-NaN, non-masked) samples in
+    /// the window, ignoring their weight magnitude. `ddof = 0` reproduces `Population` for
+    /// unit-weight kernels; `ddof = 1` gives the usual Bessel-corrected sample variance.
+    Ddof(usize),
+}
+
 /// N-dimensional sliding standard deviation operation with NaN values and a weighted kernel.
-/// NaN values are ignored.
-/// If no valid values inside a kernel window, the output is set to NaN.
-/// Gives the sliding standard deviation and the sliding mean at the same time.
+/// NaN values are ignored. If no valid values inside a kernel window, the output (and the mean)
+/// is set to NaN.
+///
+/// Uses West's (1979) weighted incremental algorithm: for each valid sample `x` with weight
+/// `w != 0`, `W += w; delta = x - mean; mean += (w / W) * delta; delta2 = x - mean;
+/// m2 += w * delta * delta2`. This replaces mixing an unweighted Welford update with per-sample
+/// weights, which is only correct for binary/uniform kernels; West's update is correct for
+/// arbitrary non-negative weights and gives a correctly weighted mean and variance in one pass.
 pub fn sliding_standard_deviation<'a>(
     workspace: &SlidingWorkspace,
     mut data: ArrayViewMutD<'a, f64>,
     mut mean_buffer: ArrayViewMutD<'a, f64>,
+    denominator: VarianceDenominator,
     neumaier: bool,
 ) {
-    // update mean buffer
-    sliding_mean(workspace, mean_buffer.view_mut(), neumaier);
-
-    // reset kernel index buffer
     let padded_strides = workspace.padded.strides();
-    // Assume everything is contiguous and abort early if it is not.
-    let padded_slice = workspace.padded.as_slice_memory_order().unwrap();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
     let has_nan = padded_slice.iter().any(|v| v.is_nan());
-    let out_slice = data.as_slice_memory_order_mut().unwrap();
-    let mean_slice = mean_buffer.as_slice_memory_order().unwrap();
+    let out_slice = data
+        .as_slice_memory_order_mut()
+        .expect("Output view must be contiguous");
+    let mean_slice = mean_buffer
+        .as_slice_memory_order_mut()
+        .expect("Mean buffer must be contiguous");
 
     let k_offsets = &workspace.kernel_offsets;
     let k_weights = &workspace.kernel_weights;
 
-    // a little less stable
-    if !neumaier {
-        // NaN check (outside of loop for efficiency)
-        if has_nan {
-            out_slice
-                .par_iter_mut()
-                .zip(mean_slice)
-                .enumerate()
-                .for_each(|(out_linear, (out, mean))| {
-                    let mut sum = 0.0;
-                    let mut sum_weights = 0.0;
-                    let base = workspace.base_offset_from_linear(out_linear, padded_strides);
-
-                    for i in 0..k_offsets.len() {
-                        let value = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
-                        if !value.is_nan() {
-                            let kernel_value = k_weights[i];
-                            let delta = value - *mean;
-                            sum += kernel_value * delta * delta;
-                            sum_weights += kernel_value;
-                        }
-                    }
+    out_slice
+        .par_iter_mut()
+        .zip(mean_slice)
+        .enumerate()
+        .for_each(|(out_linear, (out, mean_out))| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
 
-                    *out = if sum_weights == 0.0 {
-                        f64::NAN
-                    } else {
-                        (sum / sum_weights).sqrt()
-                    };
-                });
-        } else {
-            out_slice
-                .par_iter_mut()
-                .zip(mean_slice)
-                .enumerate()
-                .for_each(|(out_linear, (out, mean))| {
-                    let mut sum = 0.0;
-                    let mut sum_weights = 0.0;
-                    let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+            let mut mean = 0.0;
+            let mut m2 = 0.0;
+            let mut w_total = 0.0;
+            let mut w_c = 0.0; // Neumaier compensation terms, used only when `neumaier` is set.
+            let mut sum_w2 = 0.0;
+            let mut sum_w2_c = 0.0;
+            let mut count = 0.0;
 
-                    for i in 0..k_offsets.len() {
-                        let value = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
-                        let kernel_value = k_weights[i];
-                        let delta = value - *mean;
-                        sum += kernel_value * delta * delta;
-                        sum_weights += kernel_value;
-                    }
+            for i in 0..k_offsets.len() {
+                let kernel_value = k_weights[i];
+                if kernel_value == 0.0 {
+                    continue;
+                }
+                let value = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+                if has_nan && value.is_nan() {
+                    continue;
+                }
+                count += 1.0;
 
-                    *out = if sum_weights == 0.0 {
-                        f64::NAN
-                    } else {
-                        (sum / sum_weights).sqrt()
-                    };
-                });
-        }
-    // most stable version possible (uses Neumaier summation)
-    } else {
-        // NaN check (outside of loop for efficiency)
-        if has_nan {
-            out_slice
-                .par_iter_mut()
-                .zip(mean_slice)
-                .enumerate()
-                .for_each(|(out_linear, (out, mean))| {
-                    let mut sum = 0.0;
-                    let mut sum_weights = 0.0;
-                    let mut c = 0.0;
-                    let mut c_w = 0.0;
-                    let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+                if neumaier {
+                    neumaier_add(&mut w_total, &mut w_c, kernel_value);
+                    neumaier_add(&mut sum_w2, &mut sum_w2_c, kernel_value * kernel_value);
+                } else {
+                    w_total += kernel_value;
+                    sum_w2 += kernel_value * kernel_value;
+                }
 
-                    for i in 0..k_offsets.len() {
-                        let value = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
-                        if !value.is_nan() {
-                            let kernel_value = k_weights[i];
-                            let delta = value - *mean;
-                            neumaier_add(&mut sum, &mut c, kernel_value * delta * delta);
-                            neumaier_add(&mut sum_weights, &mut c_w, kernel_value);
-                        }
-                    }
+                let w_so_far = if neumaier { w_total + w_c } else { w_total };
+                let delta = value - mean;
+                mean += (kernel_value / w_so_far) * delta;
+                let delta2 = value - mean;
+                m2 += kernel_value * delta * delta2;
+            }
 
-                    *out = if sum_weights == 0.0 {
-                        f64::NAN
-                    } else {
-                        ((sum + c) / (sum_weights + c_w)).sqrt()
-                    };
-                });
-        } else {
-            out_slice
-                .par_iter_mut()
-                .zip(mean_slice)
-                .enumerate()
-                .for_each(|(out_linear, (out, mean))| {
-                    let mut sum = 0.0;
-                    let mut sum_weights = 0.0;
-                    let mut c = 0.0;
-                    let mut c_w = 0.0;
-                    let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+            let w = if neumaier { w_total + w_c } else { w_total };
+            let w2 = if neumaier { sum_w2 + sum_w2_c } else { sum_w2 };
 
-                    for i in 0..k_offsets.len() {
-                        let value = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
-                        let kernel_value = k_weights[i];
-                        let delta = value - *mean;
-                        neumaier_add(&mut sum, &mut c, kernel_value * delta * delta);
-                        neumaier_add(&mut sum_weights, &mut c_w, kernel_value);
-                    }
+            if w == 0.0 {
+                *mean_out = f64::NAN;
+                *out = f64::NAN;
+                return;
+            }
+            *mean_out = mean;
 
-                    *out = if sum_weights == 0.0 {
-                        f64::NAN
-                    } else {
-                        ((sum + c) / (sum_weights + c_w)).sqrt()
-                    };
-                });
-        }
-    }
+            let denom = match denominator {
+                VarianceDenominator::Population => w,
+                VarianceDenominator::ReliabilitySample => w - w2 / w,
+                VarianceDenominator::Ddof(ddof) => count - ddof as f64,
+            };
+            *out = if denom <= 0.0 {
+                f64::NAN
+            } else {
+                (m2 / denom).sqrt()
+            };
+        });
 }
@@ -0,0 +1,133 @@
+//! Fused multi-statistic sliding pass.
+//!
+//! Computes any requested subset of {mean, variance, std, min, max, valid-count} while sharing a
+//! single traversal of the padded buffer and a single Welford/West running accumulation, instead
+//! of calling the individual `sliding_mean`/`sliding_standard_deviation`/... operations (and thus
+//! re-walking every window) once per statistic.
+
+use ndarray::{ArrayD, IxDyn};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+// local
+use crate::core::padding::SlidingWorkspace;
+
+/// The statistics `sliding_stats` can compute in a single pass.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Stat {
+    Mean,
+    Variance,
+    Std,
+    Min,
+    Max,
+    Count,
+}
+
+/// Per-window running accumulation shared by every requested statistic.
+#[derive(Clone, Copy)]
+struct WindowAcc {
+    mean: f64,
+    m2: f64,
+    wsum: f64,
+    count: f64,
+    min: f64,
+    max: f64,
+}
+
+/// Computes the requested subset of `stats` over every sliding window in one walk of the padded
+/// buffer. Only the output buffers for the requested statistics are allocated; `Variance`/`Std`
+/// reuse the same West's weighted incremental (mean, M2) accumulation, and `Mean` is derived from
+/// it for free when requested alongside them.
+pub fn sliding_stats(workspace: &SlidingWorkspace, stats: &[Stat]) -> HashMap<Stat, ArrayD<f64>> {
+    let needs_min = stats.contains(&Stat::Min);
+    let needs_max = stats.contains(&Stat::Max);
+
+    let padded_strides = workspace.padded.strides();
+    let padded_slice = workspace
+        .padded
+        .as_slice_memory_order()
+        .expect("Padding buffer must be contiguous");
+    let k_offsets = &workspace.kernel_offsets;
+    let k_weights = &workspace.kernel_weights;
+    let n: usize = workspace.out_shape.iter().product();
+
+    let accs: Vec<WindowAcc> = (0..n)
+        .into_par_iter()
+        .map(|out_linear| {
+            let base = workspace.base_offset_from_linear(out_linear, padded_strides);
+
+            let mut mean = 0.0;
+            let mut m2 = 0.0;
+            let mut wsum = 0.0;
+            let mut count = 0.0;
+            let mut min = f64::NAN;
+            let mut max = f64::NAN;
+
+            for i in 0..k_offsets.len() {
+                let w = k_weights[i];
+                if w == 0.0 {
+                    continue;
+                }
+                let v = unsafe { *padded_slice.as_ptr().offset(base + k_offsets[i]) };
+                if v.is_nan() {
+                    continue;
+                }
+
+                count += 1.0;
+                // West's (1979) weighted incremental mean/variance update.
+                wsum += w;
+                let delta = v - mean;
+                mean += (w / wsum) * delta;
+                let delta2 = v - mean;
+                m2 += w * delta * delta2;
+
+                if needs_min {
+                    min = if min.is_nan() { v } else { min.min(v) };
+                }
+                if needs_max {
+                    max = if max.is_nan() { v } else { max.max(v) };
+                }
+            }
+
+            WindowAcc {
+                mean: if wsum > 0.0 { mean } else { f64::NAN },
+                m2,
+                wsum,
+                count,
+                min,
+                max,
+            }
+        })
+        .collect();
+
+    let out_shape = IxDyn(&workspace.out_shape);
+    let mut out = HashMap::new();
+    for &stat in stats {
+        let mut buf = ArrayD::from_elem(out_shape.clone(), f64::NAN);
+        let slice = buf.as_slice_memory_order_mut().unwrap();
+        for (i, acc) in accs.iter().enumerate() {
+            slice[i] = match stat {
+                Stat::Mean => acc.mean,
+                Stat::Variance => {
+                    if acc.wsum > 0.0 {
+                        acc.m2 / acc.wsum
+                    } else {
+                        f64::NAN
+                    }
+                }
+                Stat::Std => {
+                    if acc.wsum > 0.0 {
+                        (acc.m2 / acc.wsum).sqrt()
+                    } else {
+                        f64::NAN
+                    }
+                }
+                Stat::Min => acc.min,
+                Stat::Max => acc.max,
+                Stat::Count => acc.count,
+            };
+        }
+        out.insert(stat, buf);
+    }
+    out
+}
@@ -0,0 +1,44 @@
+//! Explicitly-named "weighted" entry points over `sliding_mean`/`sliding_standard_deviation`.
+//!
+//! Both of those already treat the kernel as real-valued weights (not a 0/1 mask) rather than a
+//! uniform box, the same convention `sliding_median`'s weighted half-mass quickselect uses; these
+//! are thin wrappers for callers who want to reach for "weighted mean/std" by name instead of
+//! relying on that fact.
+
+use ndarray::ArrayViewMutD;
+
+// local
+use crate::core::padding::SlidingWorkspace;
+use crate::core::sliding_mean::sliding_mean;
+use crate::core::sliding_standard_deviation::{sliding_standard_deviation, VarianceDenominator};
+
+/// N-dimensional sliding weighted mean: `mu = Sum(w_i * x_i) / Sum(w_i)` over non-NaN entries,
+/// skipping weight-0 entries exactly as `sliding_median`'s weighted path does. A thin wrapper over
+/// `sliding_mean`.
+pub fn sliding_weighted_mean<'a>(
+    workspace: &SlidingWorkspace,
+    data: ArrayViewMutD<'a, f64>,
+    neumaier: bool,
+) {
+    sliding_mean(workspace, data, neumaier, false);
+}
+
+/// N-dimensional sliding weighted standard deviation, i.e. the square root of the
+/// reliability-weighted variance `Sum(w_i * (x_i - mu)^2) / Sum(w_i)` (population, the default),
+/// or `Sum(w_i * (x_i - mu)^2) / (Sum(w_i) - Sum(w_i^2) / Sum(w_i))` when `sample_variance` is
+/// set. A thin wrapper over `sliding_standard_deviation`'s West's-algorithm implementation, which
+/// already computes this.
+pub fn sliding_weighted_standard_deviation<'a>(
+    workspace: &SlidingWorkspace,
+    data: ArrayViewMutD<'a, f64>,
+    mean_buffer: ArrayViewMutD<'a, f64>,
+    sample_variance: bool,
+    neumaier: bool,
+) {
+    let denominator = if sample_variance {
+        VarianceDenominator::ReliabilitySample
+    } else {
+        VarianceDenominator::Population
+    };
+    sliding_standard_deviation(workspace, data, mean_buffer, denominator, neumaier);
+}
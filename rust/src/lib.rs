@@ -9,11 +9,17 @@ mod core;
 
 // Re-exports
 pub use core::convolution::convolution;
-pub use core::padding::{PaddingMode, SlidingWorkspace};
+pub use core::padding::{PaddingMode, SlidingWorkspace, StatisticKind};
+pub use core::sliding_derivative::{sliding_derivative, Symmetry};
 pub use core::sliding_mean::sliding_mean;
 pub use core::sliding_median::sliding_median;
-pub use core::sliding_sigma_clipping::{CenterMode, sliding_sigma_clipping};
-pub use core::sliding_standard_deviation::sliding_standard_deviation;
+pub use core::sliding_min_max::{sliding_max, sliding_min};
+pub use core::sliding_quantile::{sliding_quantile, sliding_quantile_approx};
+pub use core::sliding_sigma_clipping::{sliding_sigma_clipping, CenterMode};
+pub use core::sliding_skewness_kurtosis::{sliding_kurtosis, sliding_skewness};
+pub use core::sliding_standard_deviation::{sliding_standard_deviation, VarianceDenominator};
+pub use core::sliding_stats::{sliding_stats, Stat};
+pub use core::sliding_weighted::{sliding_weighted_mean, sliding_weighted_standard_deviation};
 
 // Python bindings
 #[pymodule]
@@ -32,10 +38,50 @@ fn _bindings(m: &Bound<'_, PyModule>) -> PyResult<()> {
         bindings::sliding_standard_deviation::py_sliding_standard_deviation,
         m
     )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_min_max::py_sliding_min,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_min_max::py_sliding_max,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_stats::py_sliding_stats,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_quantile::py_sliding_quantile,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_quantile::py_sliding_quantile_approx,
+        m
+    )?)?;
     m.add_function(wrap_pyfunction!(
         bindings::sliding_sigma_clipping::py_sliding_sigma_clipping,
         m
     )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_skewness_kurtosis::py_sliding_skewness,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_skewness_kurtosis::py_sliding_kurtosis,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_weighted::py_sliding_weighted_mean,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_weighted::py_sliding_weighted_standard_deviation,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::sliding_derivative::py_sliding_derivative,
+        m
+    )?)?;
     Ok(())
 }
 
@@ -43,7 +89,7 @@ fn _bindings(m: &Bound<'_, PyModule>) -> PyResult<()> {
 mod tests {
     use super::*;
     use approx::assert_abs_diff_eq;
-    use ndarray::{ArrayD, arr2};
+    use ndarray::{arr1, arr2, ArrayD};
 
     fn own_data() -> (ArrayD<f64>, ArrayD<f64>, ArrayD<f64>) {
         let data = arr2(&[
@@ -58,6 +104,21 @@ mod tests {
         (data, kernel1, kernel2)
     }
 
+    /// A simple, NaN-free fixture paired with an all-ones 3x3 kernel, used to exercise the
+    /// boundary (non-`Constant`) padding modes, where `check_mean`'s NaN-bearing fixture would
+    /// make the expected values harder to follow by hand.
+    fn own_data_no_nan() -> (ArrayD<f64>, ArrayD<f64>) {
+        let data = arr2(&[
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ])
+        .into_dyn();
+        let kernel = arr2(&[[1.0, 1.0, 1.0], [1.0, 1.0, 1.0], [1.0, 1.0, 1.0]]).into_dyn();
+        (data, kernel)
+    }
+
     fn std_population(xs: &[f64]) -> f64 {
         let n = xs.len();
         if n == 0 {
@@ -84,7 +145,7 @@ mod tests {
         padded.pad_input(data.view());
 
         // compute
-        sliding_mean(&mut padded, data.view_mut());
+        sliding_mean(&padded, data.view_mut(), false, false);
 
         // compare
         let expected_mean = arr2(&[
@@ -106,7 +167,7 @@ mod tests {
         padded.pad_input(data.view());
 
         // compute
-        sliding_mean(&padded, data.view_mut());
+        sliding_mean(&padded, data.view_mut(), false, false);
 
         // compare
         let expected_mean = arr2(&[
@@ -128,7 +189,7 @@ mod tests {
         padded.pad_input(data.view());
 
         // compute
-        sliding_mean(&mut padded, data.view_mut());
+        sliding_mean(&padded, data.view_mut(), false, false);
 
         // compare
         let expected_mean = arr2(&[
@@ -141,6 +202,72 @@ mod tests {
         assert_abs_diff_eq!(data, expected_mean, epsilon = 1e-8);
     }
 
+    #[test]
+    fn check_mean_reflect() {
+        // prepare data
+        let (mut data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Reflect;
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute
+        sliding_mean(&padded, data.view_mut(), false, false);
+
+        // compare
+        let expected_mean = arr2(&[
+            [13. / 3., 14. / 3., 17. / 3., 6.],
+            [17. / 3., 6., 7., 22. / 3.],
+            [29. / 3., 10., 11., 34. / 3.],
+            [11., 34. / 3., 37. / 3., 38. / 3.],
+        ])
+        .into_dyn();
+        assert_abs_diff_eq!(data, expected_mean, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_mean_replicate() {
+        // prepare data
+        let (mut data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Replicate;
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute
+        sliding_mean(&padded, data.view_mut(), false, false);
+
+        // compare
+        let expected_mean = arr2(&[
+            [8. / 3., 10. / 3., 13. / 3., 5.],
+            [16. / 3., 6., 7., 23. / 3.],
+            [28. / 3., 10., 11., 35. / 3.],
+            [12., 38. / 3., 41. / 3., 43. / 3.],
+        ])
+        .into_dyn();
+        assert_abs_diff_eq!(data, expected_mean, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_mean_wrap() {
+        // prepare data
+        let (mut data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Wrap;
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute
+        sliding_mean(&padded, data.view_mut(), false, false);
+
+        // compare
+        let expected_mean = arr2(&[
+            [23. / 3., 22. / 3., 25. / 3., 8.],
+            [19. / 3., 6., 7., 20. / 3.],
+            [31. / 3., 10., 11., 32. / 3.],
+            [9., 26. / 3., 29. / 3., 28. / 3.],
+        ])
+        .into_dyn();
+        assert_abs_diff_eq!(data, expected_mean, epsilon = 1e-8);
+    }
+
     #[test]
     fn check_median_zero() {
         let (mut data, kernel, _) = own_data();
@@ -213,7 +340,13 @@ mod tests {
 
         // compute
         let mut mean_buffer = ArrayD::zeros(data.shape());
-        sliding_standard_deviation(&mut padded, data.view_mut(), mean_buffer.view_mut());
+        sliding_standard_deviation(
+            &padded,
+            data.view_mut(),
+            mean_buffer.view_mut(),
+            VarianceDenominator::Population,
+            false,
+        );
 
         // compare
         let expected_mean = arr2(&[
@@ -235,7 +368,13 @@ mod tests {
 
         // compute
         let mut mean_buffer = ArrayD::zeros(data.shape());
-        sliding_standard_deviation(&mut padded, data.view_mut(), mean_buffer.view_mut());
+        sliding_standard_deviation(
+            &padded,
+            data.view_mut(),
+            mean_buffer.view_mut(),
+            VarianceDenominator::Population,
+            false,
+        );
 
         // compare
         // compare
@@ -258,7 +397,13 @@ mod tests {
 
         // compute
         let mut mean_buffer = ArrayD::zeros(data.shape());
-        sliding_standard_deviation(&mut padded, data.view_mut(), mean_buffer.view_mut());
+        sliding_standard_deviation(
+            &padded,
+            data.view_mut(),
+            mean_buffer.view_mut(),
+            VarianceDenominator::Population,
+            false,
+        );
 
         // compare
         let expected_mean = arr2(&[
@@ -281,7 +426,13 @@ mod tests {
 
         // compute
         let mut mean_buffer = ArrayD::zeros(data.shape());
-        sliding_standard_deviation(&mut padded, data.view_mut(), mean_buffer.view_mut());
+        sliding_standard_deviation(
+            &padded,
+            data.view_mut(),
+            mean_buffer.view_mut(),
+            VarianceDenominator::Population,
+            false,
+        );
 
         // compare
         let std_0_0 = std_population(&[0., 0., 0., 0., 0., 2., 3., 5.]);
@@ -320,7 +471,13 @@ mod tests {
 
         // compute
         let mut mean_buffer = ArrayD::zeros(data.shape());
-        sliding_standard_deviation(&mut padded, data.view_mut(), mean_buffer.view_mut());
+        sliding_standard_deviation(
+            &padded,
+            data.view_mut(),
+            mean_buffer.view_mut(),
+            VarianceDenominator::Population,
+            false,
+        );
 
         // compare
         let std_0_0 = std_population(&[0., 0., 0., 0., 2., 5.]);
@@ -359,7 +516,13 @@ mod tests {
 
         // compute
         let mut mean_buffer = ArrayD::zeros(data.shape());
-        sliding_standard_deviation(&mut padded, data.view_mut(), mean_buffer.view_mut());
+        sliding_standard_deviation(
+            &padded,
+            data.view_mut(),
+            mean_buffer.view_mut(),
+            VarianceDenominator::Population,
+            false,
+        );
 
         // compare
         let std_0_0 = std_population(&[2., 3., 5.]);
@@ -387,4 +550,283 @@ mod tests {
         .into_dyn();
         assert_abs_diff_eq!(data, expected_std, epsilon = 1e-8);
     }
+
+    #[test]
+    fn check_min_zero() {
+        // prepare data
+        let (mut data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute
+        sliding_min(&padded, data.view_mut());
+
+        // compare
+        let expected_min = arr2(&[
+            [0., 0., 0., 0.],
+            [0., 1., 2., 0.],
+            [0., 5., 6., 0.],
+            [0., 0., 0., 0.],
+        ])
+        .into_dyn();
+        assert_abs_diff_eq!(data, expected_min, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_max_zero() {
+        // prepare data
+        let (mut data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute
+        sliding_max(&padded, data.view_mut());
+
+        // compare
+        let expected_max = arr2(&[
+            [6., 7., 8., 8.],
+            [10., 11., 12., 12.],
+            [14., 15., 16., 16.],
+            [14., 15., 16., 16.],
+        ])
+        .into_dyn();
+        assert_abs_diff_eq!(data, expected_max, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_quantile_approx_median_zero() {
+        // prepare data
+        let (mut data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute: epsilon = 0 never compresses away exact ranks, so this matches the exact
+        // median for every window (all windows here have an odd count, so there's no
+        // interpolation to disagree on).
+        sliding_quantile_approx(&padded, data.view_mut(), 0.5, 0.0);
+
+        // compare
+        let expected_median = arr2(&[
+            [0., 2., 3., 0.],
+            [2., 6., 7., 4.],
+            [6., 10., 11., 8.],
+            [0., 10., 11., 0.],
+        ])
+        .into_dyn();
+        assert_abs_diff_eq!(data, expected_median, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_derivative_linear_ramp() {
+        // prepare data: a uniform ramp, so the first-derivative is constant everywhere, including
+        // at the boundary (a forward/backward difference of a line has no truncation error).
+        let data = arr1(&[10., 20., 30., 40., 50.]).into_dyn();
+        let stencil = vec![-0.5, 0.0, 0.5];
+        let block = vec![vec![-1.0, 1.0]];
+
+        // compute
+        let result = sliding_derivative(
+            data.view(),
+            0,
+            &stencil,
+            &block,
+            Symmetry::Antisymmetric,
+            1.0,
+        );
+
+        // compare
+        let expected = arr1(&[10., 10., 10., 10., 10.]).into_dyn();
+        assert_abs_diff_eq!(result, expected, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_convolution_separable() {
+        // prepare data: a rank-1 kernel (outer product of [1,2,1] with itself), so the separable
+        // fast path is actually taken instead of silently falling back to the dense path.
+        let (data, _) = own_data_no_nan();
+        let kernel = arr2(&[[1., 2., 1.], [2., 4., 2.], [1., 2., 1.]]).into_dyn();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute: dense path vs. separable fast path on the same workspace/data.
+        let mut dense = data.clone();
+        convolution(&padded, dense.view_mut(), false, false);
+        let mut separable = data.clone();
+        convolution(&padded, separable.view_mut(), false, true);
+
+        // compare
+        assert_abs_diff_eq!(dense, separable, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_convolution_same_mode() {
+        use crate::core::padding::ConvMode;
+
+        // prepare data
+        let data = arr1(&[1., 2., 3., 4., 5.]).into_dyn();
+        let kernel = arr1(&[1., 1., 1.]).into_dyn();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode)
+            .unwrap()
+            .with_conv_mode(ConvMode::Same, vec![1], vec![1])
+            .unwrap();
+        padded.pad_input(data.view());
+
+        // compute
+        let mut result = data.clone();
+        convolution(&padded, result.view_mut(), false, false);
+
+        // compare: 'same' keeps the output length equal to the input length, padding with zeros.
+        let expected = arr1(&[3., 6., 9., 12., 9.]).into_dyn();
+        assert_abs_diff_eq!(result, expected, epsilon = 1e-8);
+    }
+
+    /// Independently re-derives skewness (`g1`) and excess kurtosis (`g2`) straight from the
+    /// central-moment definitions (no power sums), so it cross-checks the closed-form derivation
+    /// in `sliding_skewness_kurtosis` rather than just restating it.
+    fn naive_skew_kurt(xs: &[f64]) -> (f64, f64) {
+        let n = xs.len() as f64;
+        let mean = xs.iter().sum::<f64>() / n;
+        let m2 = xs.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / n;
+        let m3 = xs.iter().map(|&x| (x - mean).powi(3)).sum::<f64>() / n;
+        let m4 = xs.iter().map(|&x| (x - mean).powi(4)).sum::<f64>() / n;
+        (m3 / m2.powf(1.5), m4 / (m2 * m2) - 3.0)
+    }
+
+    #[test]
+    fn check_skewness_kurtosis_zero() {
+        // prepare data
+        let (mut data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute
+        let mut skew = data.clone();
+        sliding_skewness(&padded, skew.view_mut());
+        let mut kurt = data.clone();
+        sliding_kurtosis(&padded, kurt.view_mut());
+
+        // compare: each window is the same 3x3-with-zero-padding neighborhood already verified by
+        // `check_min_zero`/`check_max_zero`, re-derived here via the naive central-moment formula.
+        let windows: [[f64; 9]; 16] = [
+            [0., 0., 0., 0., 1., 2., 0., 5., 6.],
+            [0., 0., 0., 1., 2., 3., 5., 6., 7.],
+            [0., 0., 0., 2., 3., 4., 6., 7., 8.],
+            [0., 0., 0., 3., 4., 0., 7., 8., 0.],
+            [0., 1., 2., 0., 5., 6., 0., 9., 10.],
+            [1., 2., 3., 5., 6., 7., 9., 10., 11.],
+            [2., 3., 4., 6., 7., 8., 10., 11., 12.],
+            [3., 4., 0., 7., 8., 0., 11., 12., 0.],
+            [0., 5., 6., 0., 9., 10., 0., 13., 14.],
+            [5., 6., 7., 9., 10., 11., 13., 14., 15.],
+            [6., 7., 8., 10., 11., 12., 14., 15., 16.],
+            [7., 8., 0., 11., 12., 0., 15., 16., 0.],
+            [0., 9., 10., 0., 13., 14., 0., 0., 0.],
+            [9., 10., 11., 13., 14., 15., 0., 0., 0.],
+            [10., 11., 12., 14., 15., 16., 0., 0., 0.],
+            [11., 12., 0., 15., 16., 0., 0., 0., 0.],
+        ];
+        let mut expected_skew = vec![0.0; 16];
+        let mut expected_kurt = vec![0.0; 16];
+        for (i, w) in windows.iter().enumerate() {
+            let (g1, g2) = naive_skew_kurt(w);
+            expected_skew[i] = g1;
+            expected_kurt[i] = g2;
+        }
+        let expected_skew = ArrayD::from_shape_vec(data.raw_dim(), expected_skew).unwrap();
+        let expected_kurt = ArrayD::from_shape_vec(data.raw_dim(), expected_kurt).unwrap();
+        assert_abs_diff_eq!(skew, expected_skew, epsilon = 1e-8);
+        assert_abs_diff_eq!(kurt, expected_kurt, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_stats_zero() {
+        // prepare data
+        let (data, kernel) = own_data_no_nan();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute: the fused pass, requesting every statistic except `Variance` at once.
+        let stats = sliding_stats(
+            &padded,
+            &[Stat::Mean, Stat::Std, Stat::Min, Stat::Max, Stat::Count],
+        );
+
+        // compare against each statistic computed by its own dedicated (already-tested) sliding
+        // op, so a wiring bug reading the wrong `WindowAcc` field would show up as a mismatch.
+        let mut expected_mean = data.clone();
+        sliding_mean(&padded, expected_mean.view_mut(), false, false);
+
+        let mut expected_std = data.clone();
+        let mut mean_buffer = ArrayD::zeros(data.shape());
+        sliding_standard_deviation(
+            &padded,
+            expected_std.view_mut(),
+            mean_buffer.view_mut(),
+            VarianceDenominator::Population,
+            false,
+        );
+
+        let mut expected_min = data.clone();
+        sliding_min(&padded, expected_min.view_mut());
+
+        let mut expected_max = data.clone();
+        sliding_max(&padded, expected_max.view_mut());
+
+        let expected_count = ArrayD::from_elem(data.raw_dim(), 9.0);
+
+        assert_abs_diff_eq!(stats[&Stat::Mean], expected_mean, epsilon = 1e-8);
+        assert_abs_diff_eq!(stats[&Stat::Std], expected_std, epsilon = 1e-8);
+        assert_abs_diff_eq!(stats[&Stat::Min], expected_min, epsilon = 1e-8);
+        assert_abs_diff_eq!(stats[&Stat::Max], expected_max, epsilon = 1e-8);
+        assert_abs_diff_eq!(stats[&Stat::Count], expected_count, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn check_weighted_forwards_to_underlying() {
+        // prepare data
+        let (data, kernel1, _) = own_data();
+        let pad_mode = PaddingMode::Constant(0.);
+        let mut padded = SlidingWorkspace::new(data.shape(), kernel1, pad_mode).unwrap();
+        padded.pad_input(data.view());
+
+        // compute: `sliding_weighted_mean` should forward `neumaier` exactly like a direct
+        // `sliding_mean(..., allow_separable = false)` call.
+        let mut weighted_mean = data.clone();
+        sliding_weighted_mean(&padded, weighted_mean.view_mut(), false);
+        let mut direct_mean = data.clone();
+        sliding_mean(&padded, direct_mean.view_mut(), false, false);
+        assert_abs_diff_eq!(weighted_mean, direct_mean, epsilon = 1e-8);
+
+        // compute: `sliding_weighted_standard_deviation(sample_variance = true)` should forward to
+        // `sliding_standard_deviation` with `VarianceDenominator::ReliabilitySample`.
+        let mut weighted_std = data.clone();
+        let mut weighted_mean_buf = ArrayD::zeros(data.shape());
+        sliding_weighted_standard_deviation(
+            &padded,
+            weighted_std.view_mut(),
+            weighted_mean_buf.view_mut(),
+            true,
+            false,
+        );
+
+        let mut direct_std = data.clone();
+        let mut direct_mean_buf = ArrayD::zeros(data.shape());
+        sliding_standard_deviation(
+            &padded,
+            direct_std.view_mut(),
+            direct_mean_buf.view_mut(),
+            VarianceDenominator::ReliabilitySample,
+            false,
+        );
+
+        assert_abs_diff_eq!(weighted_std, direct_std, epsilon = 1e-8);
+        assert_abs_diff_eq!(weighted_mean_buf, direct_mean_buf, epsilon = 1e-8);
+    }
 }